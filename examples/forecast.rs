@@ -10,8 +10,12 @@ async fn main() {
     println!("report_datetime: {}", peak.report_datetime);
     println!("      area_name: {}", peak.area_name);
     println!("      area_code: {}", peak.area_code);
-    println!("         lowest: {}", peak.lowest);
-    println!("lowest_datetime: {}", peak.lowest_datetime);
-    println!("        highest: {}", peak.highest);
-    println!("highest_datetime: {}", peak.highest_datetime);
+    for day in [&peak.today, &peak.tomorrow] {
+        if let Some(lowest) = &day.lowest {
+            println!("{} lowest: {} ({})", day.date, lowest.celsius, lowest.datetime);
+        }
+        if let Some(highest) = &day.highest {
+            println!("{} highest: {} ({})", day.date, highest.celsius, highest.datetime);
+        }
+    }
 }
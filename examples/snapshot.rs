@@ -0,0 +1,42 @@
+use jma::client::{Resource, default_client};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+///
+/// Download the constant tables into the directory (default: the current directory).
+///
+/// ```console
+/// $ cargo run --example snapshot -- tables
+/// tables/area.json: 1234567 bytes
+/// tables/forecast_area.json: 123456 bytes
+/// tables/amedastable.json: 234567 bytes
+/// ```
+///
+#[tokio::main]
+async fn main() {
+    let dir = env::args().nth(1).unwrap_or(".".to_string());
+    fs::create_dir_all(&dir).unwrap();
+    let tables = [
+        (
+            "https://www.jma.go.jp/bosai/common/const/area.json",
+            "area.json",
+        ),
+        (
+            "https://www.jma.go.jp/bosai/forecast/const/forecast_area.json",
+            "forecast_area.json",
+        ),
+        (
+            "https://www.jma.go.jp/bosai/amedas/const/amedastable.json",
+            "amedastable.json",
+        ),
+    ];
+    for (url, name) in tables {
+        let body = default_client().get(url, Resource::Volatile).await.unwrap();
+        // Make sure the table still parses before replacing the saved one.
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        let path = Path::new(&dir).join(name);
+        fs::write(&path, &body).unwrap();
+        println!("{}: {} bytes", path.display(), body.len());
+    }
+}
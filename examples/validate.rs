@@ -0,0 +1,34 @@
+use jma::schema::validate_dir;
+
+///
+/// Check saved JMA payloads for schema drift.
+///
+/// ```console
+/// $ cargo run --example validate -- samples
+/// samples/020000.json ({office}.json): [0].timeSeries[0].timeDefines: missing field
+/// samples/area.json: ok
+/// ```
+///
+/// Exits with 1 if any file has issues.
+fn main() {
+    let dir = std::env::args().nth(1).unwrap_or(".".to_string());
+    let reports = validate_dir(&dir).unwrap();
+
+    let mut ok = true;
+    for report in &reports {
+        let path = report.path.display();
+        if let Some(error) = &report.error {
+            println!("{} ({}): {}", path, report.payload, error);
+        }
+        for issue in &report.issues {
+            println!("{} ({}): {}", path, report.payload, issue);
+        }
+        if report.is_ok() {
+            println!("{}: ok", path);
+        }
+        ok &= report.is_ok();
+    }
+    if !ok {
+        std::process::exit(1);
+    }
+}
@@ -1,9 +1,11 @@
 //! # Fetch AMeDAS Data
 //! 
 //! ## Example
-//! ```rust
+//! ```rust,no_run
+//! # #[cfg(feature = "http")]
 //! use jma::amedas::{station_information, Amedas, AmedasData};
 //! 
+//! # #[cfg(feature = "http")]
 //! #[tokio::main]
 //! async fn main() {
 //!     let amedas_station_sapporo = "14163";
@@ -37,6 +39,8 @@
 //!     println!("          Wind: {} {} m", latest.wind_direction_emoji, latest.wind_mps);
 //!     println!("       Weather: {}", latest.weather_discord_emoji);
 //! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
 //! ```
 //! 
 //! Output:
@@ -53,14 +57,20 @@
 //!        Weather: :sunny:
 //! ```
 
+#[cfg(feature = "http")]
+use crate::client::{default_client, ClientError, Resource};
 use std::collections::HashMap;
-use chrono::{Timelike, DateTime};
+use chrono::{Timelike, DateTime, NaiveDateTime};
+use crate::search::{to_hiragana, MatchKind};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
 pub enum AmedasError {
     ChronoParseError(chrono::format::ParseError),
+    #[cfg(feature = "http")]
     ReqwestError(reqwest::Error),
+    #[cfg(feature = "http")]
+    ClientError(ClientError),
     NoData(String),
 }
 
@@ -68,7 +78,10 @@ impl std::fmt::Display for AmedasError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AmedasError::ChronoParseError(e) => write!(f, "Chrone Parse error: {}", e),
+            #[cfg(feature = "http")]
             AmedasError::ReqwestError(e) => write!(f, "Reqwest error: {}", e),
+            #[cfg(feature = "http")]
+            AmedasError::ClientError(e) => write!(f, "Client error: {}", e),
             AmedasError::NoData(e) => write!(f, "No data: {}", e),
         }
     }
@@ -82,22 +95,35 @@ impl From<chrono::format::ParseError> for AmedasError {
     }
 }
 
+#[cfg(feature = "http")]
 impl From<reqwest::Error> for AmedasError {
     fn from(err: reqwest::Error) -> AmedasError {
         AmedasError::ReqwestError(err)
     }
 }
 
+#[cfg(feature = "http")]
+impl From<ClientError> for AmedasError {
+    fn from(err: ClientError) -> AmedasError {
+        match err {
+            ClientError::ReqwestError(e) => AmedasError::ReqwestError(e),
+            e => AmedasError::ClientError(e),
+        }
+    }
+}
+
 
 // e.g. https://www.jma.go.jp/bosai/amedas/data/point/14163/20251009_03.json
 const AMEDAS_URL: &str = "https://www.jma.go.jp/bosai/amedas/data/point";
+#[cfg(feature = "http")]
 const AMEDAS_UPDATE: &str = "https://www.jma.go.jp/bosai/amedas/data/latest_time.txt";
+#[cfg(feature = "http")]
 const AMEDAS_SITES: &str = "https://www.jma.go.jp/bosai/amedas/const/amedastable.json";
 
 
+#[cfg(feature = "http")]
 pub async fn get_latest_time() -> Result<String, AmedasError> {
-    let response = reqwest::get(AMEDAS_UPDATE).await?;
-    let body = response.text().await?;
+    let body = default_client().get_text(AMEDAS_UPDATE, Resource::Volatile).await?;
     let update = body
         .lines()
         .next()
@@ -114,15 +140,22 @@ pub fn create_amedas_url(amedas_code: &str, update_str: &str) -> Result<String,
     Ok(format!("{}/{}/{}_{:02}.json", AMEDAS_URL, amedas_code, date, multiples_of_3_hour))
 }
 
+#[cfg(feature = "http")]
 pub async fn amedas_data(url: &str) -> Result<HashMap<String, AmedasRawData>, AmedasError> {
-    let response = reqwest::get(url).await?;
-    let data = response.json::<HashMap<String, AmedasRawData>>().await?;
+    let data = default_client().get_json(url, Resource::Volatile).await?;
+    Ok(data)
+}
+
+#[cfg(feature = "http")]
+/// Fetch the AMeDAS data, cached until latest_time.txt advances.
+async fn amedas_data_at(url: &str, latest_time: &str) -> Result<HashMap<String, AmedasRawData>, AmedasError> {
+    let data = default_client().get_json(url, Resource::Amedas(latest_time.to_string())).await?;
     Ok(data)
 }
 
+#[cfg(feature = "http")]
 pub async fn station_information(amedas_id: &str) -> Result<AmedasStation, AmedasError> {
-    let response = reqwest::get(AMEDAS_SITES).await?;
-    let stations = response.json::<HashMap<String, AmedasStation>>().await?;
+    let stations = AmedasStations::new().await?;
     match stations.get(amedas_id) {
         Some(station) => return Ok(station.clone()),
         None => Err(AmedasError::NoData(format!("{} not found", amedas_id))),
@@ -268,7 +301,7 @@ pub fn weather_emoji(code: u32, emoji: [(u32, &str); 19]) -> String {
     return code_999.unwrap();
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AmedasRawData {
     pub pressure: Option<(f32, u32)>,
     pub temp: (f32, u32),
@@ -355,7 +388,7 @@ impl From<&AmedasRawData> for AmedasData {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Amedas {
     pub amedas_code: String,
     pub data: HashMap<String, AmedasRawData>,
@@ -363,13 +396,73 @@ pub struct Amedas {
 }
 
 impl Amedas {
+    #[cfg(feature = "http")]
     pub async fn new(amedas_code: &str) -> Result<Amedas, AmedasError> {
         let latest_time = get_latest_time().await?;
         let url = create_amedas_url(amedas_code, &latest_time)?;
-        let data = amedas_data(&url).await?;
+        let data = amedas_data_at(&url, &latest_time).await?;
+        Ok(Amedas { amedas_code: amedas_code.to_string(), data, latest_time })
+    }
+
+    /// Parse a point file ({amedas_code}/{YYYYMMDD}_{hh}.json).
+    ///
+    /// latest_time is taken from the newest observation.
+    pub fn from_json_str(amedas_code: &str, json: &str) -> Result<Amedas, serde_json::Error> {
+        Ok(Amedas::from_data(amedas_code, serde_json::from_str(json)?))
+    }
+
+    /// Parse a point file from the reader.
+    pub fn from_reader<R: std::io::Read>(amedas_code: &str, reader: R) -> Result<Amedas, serde_json::Error> {
+        Ok(Amedas::from_data(amedas_code, serde_json::from_reader(reader)?))
+    }
+
+    /// Convert a parsed point file.
+    pub fn from_value(amedas_code: &str, json: serde_json::Value) -> Result<Amedas, serde_json::Error> {
+        Ok(Amedas::from_data(amedas_code, serde_json::from_value(json)?))
+    }
+
+    /// Parse and merge point files, e.g. the 3-hour files of a day.
+    pub fn from_json_strs<'a, I>(amedas_code: &str, jsons: I) -> Result<Amedas, serde_json::Error>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut data = HashMap::new();
+        for json in jsons {
+            let file: HashMap<String, AmedasRawData> = serde_json::from_str(json)?;
+            data.extend(file);
+        }
+        Ok(Amedas::from_data(amedas_code, data))
+    }
+
+    fn from_data(amedas_code: &str, data: HashMap<String, AmedasRawData>) -> Amedas {
+        let latest_time = data
+            .keys()
+            .max()
+            .and_then(|key| NaiveDateTime::parse_from_str(key, "%Y%m%d%H%M%S").ok())
+            .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S+09:00").to_string())
+            .unwrap_or_default();
+        Amedas { amedas_code: amedas_code.to_string(), data, latest_time }
+    }
+
+    /// Fetch the observations of the last hours, merging the 3-hour files.
+    #[cfg(feature = "http")]
+    pub async fn history(amedas_code: &str, hours: u32) -> Result<Amedas, AmedasError> {
+        let latest_time = get_latest_time().await?;
+        let latest = DateTime::parse_from_rfc3339(&latest_time)?;
+        let mut data = HashMap::new();
+        for i in 0..=hours.div_ceil(3) {
+            let time = latest - chrono::Duration::hours(3 * i as i64);
+            let url = create_amedas_url(amedas_code, &time.to_rfc3339())?;
+            data.extend(amedas_data_at(&url, &latest_time).await?);
+        }
+        let oldest = (latest - chrono::Duration::hours(hours as i64))
+            .format("%Y%m%d%H%M%S")
+            .to_string();
+        data.retain(|datetime, _| *datetime > oldest);
         Ok(Amedas { amedas_code: amedas_code.to_string(), data, latest_time })
     }
 
+    #[cfg(feature = "http")]
     pub async fn update(&mut self) -> Result<bool, AmedasError> {
         let latest_time= get_latest_time().await?;
         if latest_time == self.latest_time {
@@ -377,7 +470,7 @@ impl Amedas {
         }
         
         let url = create_amedas_url(&self.amedas_code, &latest_time)?;
-        let data = amedas_data(&url).await?;
+        let data = amedas_data_at(&url, &latest_time).await?;
 
         self.data = data;
         self.latest_time = latest_time.clone();
@@ -434,6 +527,78 @@ pub struct AmedasStation {
     pub english_name: String,
 }
 
+/// AMeDAS stations in amedastable.json keyed by the station code.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AmedasStations {
+    #[serde(flatten)]
+    pub stations: HashMap<String, AmedasStation>,
+}
+
+impl AmedasStations {
+    /// Fetch amedastable.json.
+    #[cfg(feature = "http")]
+    pub async fn new() -> Result<AmedasStations, ClientError> {
+        default_client().get_json(AMEDAS_SITES, Resource::Constant).await
+    }
+
+    /// Parse amedastable.json.
+    pub fn from_json_str(json: &str) -> Result<AmedasStations, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse amedastable.json from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<AmedasStations, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed amedastable.json.
+    pub fn from_value(json: serde_json::Value) -> Result<AmedasStations, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+
+    /// Get the station.
+    pub fn get(&self, amedas_code: &str) -> Option<&AmedasStation> {
+        self.stations.get(amedas_code)
+    }
+
+    /// Returns the stations whose code, kanji, kana or English name contains the keyword.
+    ///
+    /// Exact matches come first, then prefix and substring matches, each ordered by code.
+    pub fn search(&self, keyword: &str) -> Vec<(&String, &AmedasStation)> {
+        let hiragana = to_hiragana(keyword);
+        let lower = keyword.to_lowercase();
+        let kind = |key: &str, keyword: &str| {
+            if key == keyword {
+                Some(MatchKind::Exact)
+            } else if key.starts_with(keyword) {
+                Some(MatchKind::Prefix)
+            } else if key.contains(keyword) {
+                Some(MatchKind::Substring)
+            } else {
+                None
+            }
+        };
+        let mut hits: Vec<(MatchKind, &String, &AmedasStation)> = self
+            .stations
+            .iter()
+            .filter_map(|(code, s)| {
+                let best = [
+                    kind(code, keyword),
+                    kind(&s.kanji_name, keyword),
+                    kind(&to_hiragana(&s.kana_name), &hiragana),
+                    kind(&s.english_name.to_lowercase(), &lower),
+                ]
+                .into_iter()
+                .flatten()
+                .min()?;
+                Some((best, code, s))
+            })
+            .collect();
+        hits.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        hits.into_iter().map(|(_, code, s)| (code, s)).collect()
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -455,6 +620,48 @@ mod tests {
         assert!(create_amedas_url(amedas_code, latest_time_parse_error_str).is_err());
     }
     
+    #[test]
+    fn test_from_json_strs() {
+        let raw = r#"{"temp": [1.0, 0], "humidity": [50.0, 0], "precipitation10m": [0.0, 0],
+            "precipitation1h": [0.0, 0], "windDirection": [4, 0], "wind": [1.5, 0]}"#;
+        let file00 = format!(r#"{{"20251118000000": {raw}, "20251118021000": {raw}}}"#);
+        let file03 = format!(r#"{{"20251118030000": {raw}, "20251118031000": {raw}}}"#);
+        let amedas = Amedas::from_json_strs("14163", [file00.as_str(), file03.as_str()]).unwrap();
+        assert_eq!(amedas.data.len(), 4);
+        assert_eq!(amedas.latest_time, "2025-11-18T03:10:00+09:00");
+        assert!(amedas.get_latest_data().is_some());
+
+        let amedas = Amedas::from_reader("14163", file00.as_bytes()).unwrap();
+        assert_eq!(amedas.latest_time, "2025-11-18T02:10:00+09:00");
+        assert!(Amedas::from_json_str("14163", "[]").is_err());
+    }
+
+    #[test]
+    fn test_search_stations() {
+        let station = |kj: &str, kn: &str, en: &str| serde_json::json!({
+            "type": "A", "elems": "11111111", "lat": [43, 3.6], "lon": [141, 19.7], "alt": 17,
+            "kjName": kj, "knName": kn, "enName": en
+        });
+        let stations = AmedasStations::from_value(serde_json::json!({
+            "14163": station("札幌", "サッポロ", "Sapporo"),
+            "14121": station("新篠津", "シンシノツ", "Shinshinotsu"),
+            "14136": station("北札幌", "キタサッポロ", "Kita-Sapporo"),
+            "14200": station("篠路", "シノロ", "Shinoro")
+        }))
+        .unwrap();
+        let codes = |keyword: &str| -> Vec<String> {
+            stations.search(keyword).into_iter().map(|(code, _)| code.clone()).collect()
+        };
+        assert_eq!(codes("札幌"), ["14163", "14136"]);
+        assert_eq!(codes("さっぽろ"), ["14163", "14136"]);
+        assert_eq!(codes("サッポロ"), ["14163", "14136"]);
+        assert_eq!(codes("しの"), ["14200", "14121"]);
+        assert_eq!(codes("sapporo"), ["14163", "14136"]);
+        assert_eq!(codes("14121"), ["14121"]);
+        assert!(codes("東京").is_empty());
+    }
+
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn test_latest() {
         let amedas = Amedas::new("14163").await.unwrap();
@@ -462,6 +669,7 @@ mod tests {
         println!("amedas: {:?}", data);
     }
 
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn test_svg_url() {
         // Day
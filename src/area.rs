@@ -73,9 +73,10 @@
 //! ```
 //!
 //! ## Example
-//! ```rust
+//! ```rust,no_run
 //! use jma::area::{Areas, JmaAreaClass};
 //!
+//! # #[cfg(feature = "http")]
 //! #[tokio::main]
 //! async fn main() {
 //!     // Fetch area.json
@@ -106,6 +107,13 @@
 //!     assert_eq!(a.class, JmaAreaClass::Office);
 //!     assert_eq!(a.code, "100000");
 //!
+//!     // Municipalities in the office and the path from the Center
+//!     let cities = areas.descendants(&a, &JmaAreaClass::Class20);
+//!     assert!(cities.iter().any(|c| c.code == "1020100"));
+//!     let path = areas.path(&v);
+//!     assert_eq!(path[0].class, JmaAreaClass::Center);
+//!     assert_eq!(path[4].code, "1020100");
+//!
 //!     // Search keyword '100011'.
 //!     let k = areas.search("100011");
 //!     assert_eq!(k.len(), 1);
@@ -126,15 +134,19 @@
 //!         ])
 //!     );
 //! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
 //! ```
 
-use reqwest::Error;
-use serde::Deserialize;
-use serde_json::Value;
+#[cfg(feature = "http")]
+use crate::client::{default_client, ClientError, Resource};
+use crate::search::{SearchHit, SearchIndex};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::OnceLock;
 
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub enum JmaAreaClass {
     Center,
     Office,
@@ -194,7 +206,7 @@ impl fmt::Display for JmaAreaClass {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RawArea {
     pub name: String,
@@ -205,7 +217,7 @@ pub struct RawArea {
     pub children: Option<Vec<String>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Area {
     pub area: RawArea,
     pub class: JmaAreaClass,
@@ -222,22 +234,38 @@ impl Area {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Areas {
     centers: HashMap<String, RawArea>,
     offices: HashMap<String, RawArea>,
     class10s: HashMap<String, RawArea>,
     class15s: HashMap<String, RawArea>,
     class20s: HashMap<String, RawArea>,
+    #[serde(skip)]
+    index: OnceLock<SearchIndex>,
 }
 
 impl Areas {
     /// Fetch area.json from JMA and parse it.
-    pub async fn new() -> Result<Areas, Error> {
+    #[cfg(feature = "http")]
+    pub async fn new() -> Result<Areas, ClientError> {
         let url = "https://www.jma.go.jp/bosai/common/const/area.json";
-        let area_json = reqwest::get(url).await?.json::<Value>().await?;
-        let areas: Areas = serde_json::from_value(area_json.clone()).unwrap();
-        Ok(areas)
+        default_client().get_json(url, Resource::Constant).await
+    }
+
+    /// Parse area.json.
+    pub fn from_json_str(json: &str) -> Result<Areas, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse area.json from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Areas, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed area.json.
+    pub fn from_value(json: serde_json::Value) -> Result<Areas, serde_json::Error> {
+        serde_json::from_value(json)
     }
 
     /// Get area information.
@@ -266,56 +294,32 @@ impl Areas {
         }
     }
 
-    /// Returns the area if the beginning of the name, en_name, or code string contains the key.
+    /// Returns the search index, built on the first search.
+    pub fn search_index(&self) -> &SearchIndex {
+        self.index.get_or_init(|| SearchIndex::new(self))
+    }
+
+    /// Returns the areas matching the code, name, kana, en_name or romaji, best first.
+    ///
+    /// See [`crate::search`] for the ranking.
     pub fn search(&self, keyword: &str) -> Vec<Area> {
-        let mut result = Vec::new();
-        for class in [
-            JmaAreaClass::Center,
-            JmaAreaClass::Office,
-            JmaAreaClass::Class10,
-            JmaAreaClass::Class15,
-            JmaAreaClass::Class20,
-        ] {
-            let cls = self.areas(&class);
-            for (key, value) in cls {
-                if key == keyword {
-                    result.push(self.values(&class, &key).unwrap());
-                }
-                for k in ["name", "en_name"] {
-                    let v = match k {
-                        "name" => &value.name,
-                        "en_name" => &value.en_name.to_lowercase(),
-                        _ => panic!("key '{}' not found", k),
-                    };
-                    if v.starts_with(&keyword.to_lowercase()) {
-                        result.push(self.values(&class, &key).unwrap());
-                    }
-                }
-            }
-        }
-        result
+        self.search_in(keyword, None)
+            .into_iter()
+            .map(|hit| hit.area)
+            .collect()
     }
 
-    /// Returns the area if the beginning of the name, en_name, or code string in class20 contains the key.
-    pub fn search_class20s(&self, keyword: &str) -> Vec<Area> {
-        let mut result = Vec::new();
+    /// Returns the areas in the class (or any class) matching the keyword with the match kind.
+    pub fn search_in(&self, keyword: &str, class: Option<&JmaAreaClass>) -> Vec<SearchHit> {
+        self.search_index().search(keyword, class)
+    }
 
-        for (key, value) in &self.class20s {
-            if key == keyword {
-                result.push(self.values(&JmaAreaClass::Class20, &key).unwrap());
-            }
-            for k in ["name", "en_name"] {
-                let v = match k {
-                    "name" => &value.name,
-                    "en_name" => &value.en_name.to_lowercase(),
-                    _ => panic!("key '{}' not found", k),
-                };
-                if v.starts_with(&keyword.to_lowercase()) {
-                    result.push(self.values(&JmaAreaClass::Class20, &key).unwrap());
-                }
-            }
-        }
-        result
+    /// Returns the areas in class20 matching the keyword, best first.
+    pub fn search_class20s(&self, keyword: &str) -> Vec<Area> {
+        self.search_in(keyword, Some(&JmaAreaClass::Class20))
+            .into_iter()
+            .map(|hit| hit.area)
+            .collect()
     }
 
     /// Returns the area's parent.
@@ -344,12 +348,135 @@ impl Areas {
         }
         None
     }
+
+    /// Returns the area's children in the order of `children`.
+    pub fn children(&self, area: &Area) -> Vec<Area> {
+        let class = match area.class.child() {
+            Some(class) => class,
+            None => return Vec::new(),
+        };
+        match &area.area.children {
+            Some(codes) => codes
+                .iter()
+                .filter_map(|code| self.values(&class, code))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the area's descendants in the JmaAreaClass.
+    ///
+    /// Returns the area itself if it is in the class, and nothing if the class is not below it.
+    pub fn descendants(&self, area: &Area, class: &JmaAreaClass) -> Vec<Area> {
+        let mut areas = vec![area.clone()];
+        let mut current = area.class.clone();
+        while current != *class {
+            current = match current.child() {
+                Some(child) => child,
+                None => return Vec::new(),
+            };
+            areas = areas.iter().flat_map(|a| self.children(a)).collect();
+        }
+        areas
+    }
+
+    /// Returns the areas from the Center down to the area.
+    pub fn path(&self, area: &Area) -> Vec<Area> {
+        let mut path = vec![area.clone()];
+        while let Some(parent) = self.parent(path.last().unwrap()) {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the other children of the area's parent.
+    ///
+    /// The siblings of a Center are the other Centers, ordered by code.
+    pub fn siblings(&self, area: &Area) -> Vec<Area> {
+        let areas = match self.parent(area) {
+            Some(parent) => self.children(&parent),
+            None => {
+                let mut codes: Vec<&String> = self.areas(&area.class).keys().collect();
+                codes.sort();
+                codes
+                    .into_iter()
+                    .filter_map(|code| self.values(&area.class, code))
+                    .collect()
+            }
+        };
+        areas.into_iter().filter(|a| a.code != area.code).collect()
+    }
+
+    /// Returns the lowest area which has both areas as descendants (or is one of them).
+    pub fn lowest_common_ancestor(&self, a: &Area, b: &Area) -> Option<Area> {
+        let path_a = self.path(a);
+        let path_b = self.path(b);
+        path_a
+            .into_iter()
+            .zip(path_b)
+            .take_while(|(a, b)| a.class == b.class && a.code == b.code)
+            .last()
+            .map(|(a, _)| a)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_json_str() {
+        let json = r#"{
+            "centers": {},
+            "offices": {"100000": {"name": "群馬県", "enName": "Gunma", "officeName": "前橋地方気象台", "parent": "010300", "children": ["100010", "100020"]}},
+            "class10s": {},
+            "class15s": {},
+            "class20s": {}
+        }"#;
+        let areas = Areas::from_json_str(json).unwrap();
+        let v = areas.values(&JmaAreaClass::Office, "100000").unwrap();
+        assert_eq!(v.area.en_name, "Gunma");
+        let areas = Areas::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(areas.areas(&JmaAreaClass::Office).len(), 1);
+        assert!(Areas::from_value(serde_json::json!({"centers": {}})).is_err());
+    }
+
+    fn codes(areas: &[Area]) -> Vec<&str> {
+        areas.iter().map(|a| a.code.as_str()).collect()
+    }
+
+    #[test]
+    fn hierarchy() {
+        let areas = Areas::from_json_str(include_str!("../testdata/area.json")).unwrap();
+        let gunma = areas.values(&JmaAreaClass::Office, "100000").unwrap();
+        assert_eq!(codes(&areas.children(&gunma)), ["100010", "100020"]);
+        let cities = areas.descendants(&gunma, &JmaAreaClass::Class20);
+        assert_eq!(cities.len(), 6);
+        assert_eq!(cities[0].area.name, "前橋市");
+        assert!(areas.descendants(&gunma, &JmaAreaClass::Center).is_empty());
+        assert_eq!(codes(&areas.descendants(&gunma, &JmaAreaClass::Office)), ["100000"]);
+
+        let maebashi = areas.values(&JmaAreaClass::Class20, "1020100").unwrap();
+        assert!(areas.children(&maebashi).is_empty());
+        assert_eq!(
+            codes(&areas.path(&maebashi)),
+            ["010300", "100000", "100010", "100011", "1020100"]
+        );
+        assert_eq!(areas.siblings(&maebashi).len(), 5);
+        assert_eq!(codes(&areas.siblings(&gunma)), ["200000"]);
+
+        let kiryu = areas.values(&JmaAreaClass::Class20, "1020300").unwrap();
+        let lca = areas.lowest_common_ancestor(&maebashi, &kiryu).unwrap();
+        assert_eq!(lca.code, "100011");
+        let nagano = areas.values(&JmaAreaClass::Class10, "200010").unwrap();
+        let lca = areas.lowest_common_ancestor(&maebashi, &nagano).unwrap();
+        assert_eq!(lca.class, JmaAreaClass::Center);
+        let lca = areas.lowest_common_ancestor(&gunma, &maebashi).unwrap();
+        assert_eq!(lca.code, "100000");
+    }
+
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn values() {
         let areas = Areas::new().await.unwrap();
@@ -364,6 +491,7 @@ mod tests {
         assert_eq!(v.code, "1020100");
     }
 
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn ancestor() {
         let areas = Areas::new().await.unwrap();
@@ -387,6 +515,7 @@ mod tests {
         assert_eq!(a.code, "400000");
     }
 
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn not_ancestor() {
         let areas = Areas::new().await.unwrap();
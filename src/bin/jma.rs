@@ -0,0 +1,426 @@
+//! Command line tool for the JMA data.
+//!
+//! ```console
+//! $ jma area search 前橋
+//! $ jma forecast short 前橋市
+//! $ jma amedas latest 札幌
+//! ```
+//! Areas and stations are given by code or by name (kanji, kana, English or romaji).
+//! The results are printed as a table, or with `--format json|ndjson|csv|yaml`.
+
+use chrono::NaiveDateTime;
+use clap::{Parser, Subcommand};
+use jma::amedas::{Amedas, AmedasData, AmedasStation, AmedasStations};
+use jma::area::{Area, Areas, JmaAreaClass};
+use jma::forecast::{DailyWeather, JmaForecast, WeeklyDay};
+use jma::output::{OutputFormat, render};
+use jma::warning::{JmaWarning, Warning};
+use serde::Serialize;
+use std::error::Error;
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "jma",
+    version,
+    about = "Query the Japan Meteorological Agency data"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// table, json, ndjson, csv or yaml
+    #[arg(long, short, global = true, default_value = "table", value_parser = parse_format)]
+    format: OutputFormat,
+}
+
+fn parse_format(format: &str) -> Result<OutputFormat, String> {
+    OutputFormat::to_output_format(format).ok_or_else(|| format!("unknown format: {}", format))
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Forecast areas (area.json)
+    #[command(subcommand)]
+    Area(AreaCommand),
+    /// AMeDAS stations (amedastable.json)
+    #[command(subcommand)]
+    Station(StationCommand),
+    /// AMeDAS observations
+    #[command(subcommand)]
+    Amedas(AmedasCommand),
+    /// Forecasts of an area
+    #[command(subcommand)]
+    Forecast(ForecastCommand),
+    /// Warnings and advisories in effect in an area
+    Warnings {
+        /// Area code or name
+        area: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AreaCommand {
+    /// Search areas by code or name, best first
+    Search {
+        keyword: String,
+        /// center, office, class10, class15 or class20
+        #[arg(long)]
+        class: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Show an area with its ancestors and children
+    Show {
+        /// Area code or name
+        area: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum StationCommand {
+    /// Search stations by code or name
+    Search { keyword: String },
+}
+
+#[derive(Subcommand)]
+enum AmedasCommand {
+    /// The latest observation of a station
+    Latest {
+        /// Station code or name
+        station: String,
+    },
+    /// The observations of the last hours
+    History {
+        /// Station code or name
+        station: String,
+        #[arg(long, default_value_t = 3)]
+        hours: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum ForecastCommand {
+    /// Today, tomorrow and the day after tomorrow
+    Short {
+        /// Area code or name (Class10, Class15 or Class20)
+        area: String,
+    },
+    /// Seven days
+    Weekly {
+        /// Area code or name (Class10, Class15 or Class20)
+        area: String,
+    },
+}
+
+/// Resolve the area by code or name, taking the best match.
+fn resolve_area(areas: &Areas, keyword: &str) -> Result<Area, Box<dyn Error>> {
+    areas
+        .search(keyword)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("area not found: {}", keyword).into())
+}
+
+/// Resolve the station by code or name, taking the best match.
+fn resolve_station(
+    stations: &AmedasStations,
+    keyword: &str,
+) -> Result<(String, AmedasStation), Box<dyn Error>> {
+    stations
+        .search(keyword)
+        .into_iter()
+        .next()
+        .map(|(code, station)| (code.clone(), station.clone()))
+        .ok_or_else(|| format!("station not found: {}", keyword).into())
+}
+
+fn print<T: Serialize>(records: &[T], format: OutputFormat) -> CliResult {
+    print!("{}", render(records, format)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct AreaRow {
+    /// ancestor, area or child
+    relation: &'static str,
+    #[serde(flatten)]
+    area: Area,
+}
+
+#[derive(Serialize)]
+struct StationRow<'a> {
+    code: &'a str,
+    #[serde(flatten)]
+    station: &'a AmedasStation,
+}
+
+#[derive(Serialize)]
+struct ObservationRow {
+    station: String,
+    name: String,
+    datetime: String,
+    temp_c: f32,
+    humidity_percent: f32,
+    pressure_hpa: Option<f32>,
+    precipitation10m: f32,
+    precipitation1h: f32,
+    wind_direction: String,
+    wind_mps: f32,
+    weather: Option<u32>,
+    snow1h: Option<f32>,
+    visibility_m: Option<f32>,
+}
+
+impl ObservationRow {
+    fn new(code: &str, station: &AmedasStation, datetime: &str, data: AmedasData) -> Self {
+        ObservationRow {
+            station: code.to_string(),
+            name: station.kanji_name.clone(),
+            datetime: datetime.to_string(),
+            temp_c: data.temp_c,
+            humidity_percent: data.humidity_percent,
+            pressure_hpa: data.pressure_hpa,
+            precipitation10m: data.precipitation10m,
+            precipitation1h: data.precipitation1h,
+            wind_direction: data.wind_direction_str,
+            wind_mps: data.wind_mps,
+            weather: data.weather,
+            snow1h: data.snow1h,
+            visibility_m: data.visibility_m,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ShortTermRow<'a> {
+    report_datetime: &'a str,
+    area_code: &'a str,
+    area_name: &'a str,
+    #[serde(flatten)]
+    day: &'a DailyWeather,
+    /// The 6-hour probabilities of precipitation of the day (%).
+    pops: Vec<Option<u32>>,
+}
+
+#[derive(Serialize)]
+struct WeeklyRow<'a> {
+    report_datetime: &'a str,
+    area_code: &'a str,
+    area_name: &'a str,
+    temp_area_name: Option<&'a str>,
+    #[serde(flatten)]
+    day: &'a WeeklyDay,
+}
+
+#[derive(Serialize)]
+struct WarningRow<'a> {
+    report_datetime: &'a str,
+    area_code: String,
+    area_name: String,
+    #[serde(flatten)]
+    warning: Warning,
+}
+
+/// "20251118103000" to "2025-11-18T10:30:00+09:00"
+fn observation_time(key: &str) -> String {
+    NaiveDateTime::parse_from_str(key, "%Y%m%d%H%M%S")
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S+09:00").to_string())
+        .unwrap_or(key.to_string())
+}
+
+async fn area_command(command: AreaCommand, format: OutputFormat) -> CliResult {
+    let areas = Areas::new().await?;
+    match command {
+        AreaCommand::Search {
+            keyword,
+            class,
+            limit,
+        } => {
+            let class = match class {
+                Some(class) => Some(
+                    JmaAreaClass::to_jma_area_class(&class)
+                        .ok_or_else(|| format!("unknown class: {}", class))?,
+                ),
+                None => None,
+            };
+            let found: Vec<Area> = areas
+                .search_in(&keyword, class.as_ref())
+                .into_iter()
+                .take(limit)
+                .map(|hit| hit.area)
+                .collect();
+            print(&found, format)
+        }
+        AreaCommand::Show { area } => {
+            let area = resolve_area(&areas, &area)?;
+            let mut rows: Vec<AreaRow> = areas
+                .path(&area)
+                .into_iter()
+                .map(|a| AreaRow {
+                    relation: if a.code == area.code {
+                        "area"
+                    } else {
+                        "ancestor"
+                    },
+                    area: a,
+                })
+                .collect();
+            rows.extend(areas.children(&area).into_iter().map(|a| AreaRow {
+                relation: "child",
+                area: a,
+            }));
+            print(&rows, format)
+        }
+    }
+}
+
+async fn station_command(command: StationCommand, format: OutputFormat) -> CliResult {
+    let stations = AmedasStations::new().await?;
+    match command {
+        StationCommand::Search { keyword } => {
+            let rows: Vec<StationRow> = stations
+                .search(&keyword)
+                .into_iter()
+                .map(|(code, station)| StationRow { code, station })
+                .collect();
+            print(&rows, format)
+        }
+    }
+}
+
+async fn amedas_command(command: AmedasCommand, format: OutputFormat) -> CliResult {
+    let stations = AmedasStations::new().await?;
+    match command {
+        AmedasCommand::Latest { station } => {
+            let (code, station) = resolve_station(&stations, &station)?;
+            let amedas = Amedas::new(&code).await?;
+            let latest = amedas
+                .get_latest_data()
+                .ok_or_else(|| format!("no observation: {}", code))?;
+            let row = ObservationRow::new(
+                &code,
+                &station,
+                &amedas.latest_time,
+                AmedasData::from(&latest),
+            );
+            print(&[row], format)
+        }
+        AmedasCommand::History { station, hours } => {
+            let (code, station) = resolve_station(&stations, &station)?;
+            let amedas = Amedas::history(&code, hours).await?;
+            let mut datetimes: Vec<&String> = amedas.data.keys().collect();
+            datetimes.sort();
+            let rows: Vec<ObservationRow> = datetimes
+                .into_iter()
+                .map(|datetime| {
+                    ObservationRow::new(
+                        &code,
+                        &station,
+                        &observation_time(datetime),
+                        AmedasData::from(&amedas.data[datetime]),
+                    )
+                })
+                .collect();
+            print(&rows, format)
+        }
+    }
+}
+
+async fn forecast_command(command: ForecastCommand, format: OutputFormat) -> CliResult {
+    let areas = Areas::new().await?;
+    let (keyword, weekly) = match &command {
+        ForecastCommand::Short { area } => (area, false),
+        ForecastCommand::Weekly { area } => (area, true),
+    };
+    let area = resolve_area(&areas, keyword)?;
+    let forecast = JmaForecast::for_area(&areas, &area)
+        .await?
+        .ok_or_else(|| format!("no forecast for {} {}", area.code, area.area.name))?;
+
+    if !weekly {
+        let short = &forecast.short_term;
+        let rows: Vec<ShortTermRow> = short
+            .days
+            .iter()
+            .map(|day| ShortTermRow {
+                report_datetime: &short.report_datetime,
+                area_code: &short.area_code,
+                area_name: &short.area_name,
+                day,
+                pops: short
+                    .pops
+                    .iter()
+                    .filter(|pop| pop.datetime.get(..10) == day.datetime.get(..10))
+                    .map(|pop| pop.pop)
+                    .collect(),
+            })
+            .collect();
+        return print(&rows, format);
+    }
+
+    let weekly = forecast
+        .weekly
+        .as_ref()
+        .ok_or_else(|| format!("no weekly forecast for {}", area.code))?;
+    let rows: Vec<WeeklyRow> = weekly
+        .days
+        .iter()
+        .map(|day| WeeklyRow {
+            report_datetime: &weekly.report_datetime,
+            area_code: &weekly.area_code,
+            area_name: &weekly.area_name,
+            temp_area_name: weekly.temp_area_name.as_deref(),
+            day,
+        })
+        .collect();
+    print(&rows, format)
+}
+
+async fn warnings_command(area: &str, format: OutputFormat) -> CliResult {
+    let areas = Areas::new().await?;
+    let area = resolve_area(&areas, area)?;
+    let office = areas
+        .ancestor(&area, &JmaAreaClass::Office)
+        .ok_or_else(|| format!("no office for {} {}", area.code, area.area.name))?;
+    let warning = JmaWarning::new(&office.code).await?;
+
+    // Warnings are published for the Class10 and Class20 areas.
+    let targets = match area.class {
+        JmaAreaClass::Office => areas.children(&area),
+        JmaAreaClass::Class15 => areas
+            .ancestor(&area, &JmaAreaClass::Class10)
+            .into_iter()
+            .collect(),
+        _ => vec![area],
+    };
+    let mut rows = Vec::new();
+    for target in targets {
+        for w in warning.warnings(&target.code) {
+            rows.push(WarningRow {
+                report_datetime: &warning.report_datetime,
+                area_code: target.code.clone(),
+                area_name: target.area.name.clone(),
+                warning: w,
+            });
+        }
+    }
+    print(&rows, format)
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let format = cli.format;
+    let result = match cli.command {
+        Command::Area(command) => area_command(command, format).await,
+        Command::Station(command) => station_command(command, format).await,
+        Command::Amedas(command) => amedas_command(command, format).await,
+        Command::Forecast(command) => forecast_command(command, format).await,
+        Command::Warnings { area } => warnings_command(&area, format).await,
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
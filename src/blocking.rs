@@ -0,0 +1,265 @@
+//! # Blocking API
+//!
+//! Synchronous counterparts of the fetching APIs for programs without an async runtime.
+//! Enabled by the `blocking` feature.
+//!
+//! Each type wraps the async type and dereferences to it, so everything except fetching
+//! works the same way. The requests go through the same [`JmaClient`](crate::client::JmaClient)
+//! (cache, retry and rate limit) on a runtime shared by the calling threads.
+//!
+//! As with `reqwest::blocking`, these must not be called from within an async runtime.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::area::JmaAreaClass;
+//! use jma::blocking::{Amedas, Areas};
+//!
+//! fn main() {
+//!     let areas = Areas::new().unwrap();
+//!     let v = areas.values(&JmaAreaClass::Class20, "1020100").unwrap();
+//!     assert_eq!(v.area.name, "前橋市");
+//!
+//!     let mut amedas = Amedas::new("14163").unwrap();
+//!     amedas.update().unwrap();
+//!     println!("{:?}", amedas.get_latest_data());
+//! }
+//! ```
+
+use crate::amedas::{AmedasError, AmedasStation};
+use crate::client::ClientError;
+use crate::forecast::AreaForecast;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
+/// The runtime shared by all threads.
+///
+/// The pooled connections of the client are driven by its worker, so a connection opened on
+/// one thread keeps working after that thread goes idle.
+static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+/// Run the future to completion on the shared runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("jma-blocking")
+                .enable_all()
+                .build()
+                .expect("failed to build the tokio runtime")
+        })
+        .block_on(future)
+}
+
+/// Blocking version of [`crate::amedas::get_latest_time`].
+pub fn get_latest_time() -> Result<String, AmedasError> {
+    block_on(crate::amedas::get_latest_time())
+}
+
+/// Blocking version of [`crate::amedas::station_information`].
+pub fn station_information(amedas_id: &str) -> Result<AmedasStation, AmedasError> {
+    block_on(crate::amedas::station_information(amedas_id))
+}
+
+/// Blocking version of [`crate::amedas::Amedas`].
+#[derive(Debug, Clone)]
+pub struct Amedas(crate::amedas::Amedas);
+
+impl Amedas {
+    pub fn new(amedas_code: &str) -> Result<Amedas, AmedasError> {
+        block_on(crate::amedas::Amedas::new(amedas_code)).map(Amedas)
+    }
+
+    pub fn update(&mut self) -> Result<bool, AmedasError> {
+        block_on(self.0.update())
+    }
+
+    pub fn into_inner(self) -> crate::amedas::Amedas {
+        self.0
+    }
+}
+
+impl Deref for Amedas {
+    type Target = crate::amedas::Amedas;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Amedas {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Blocking version of [`crate::amedas::AmedasStations`].
+#[derive(Debug, Clone)]
+pub struct AmedasStations(crate::amedas::AmedasStations);
+
+impl AmedasStations {
+    /// Fetch amedastable.json.
+    pub fn new() -> Result<AmedasStations, ClientError> {
+        block_on(crate::amedas::AmedasStations::new()).map(AmedasStations)
+    }
+
+    pub fn into_inner(self) -> crate::amedas::AmedasStations {
+        self.0
+    }
+}
+
+impl Deref for AmedasStations {
+    type Target = crate::amedas::AmedasStations;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Blocking version of [`crate::area::Areas`].
+#[derive(Debug)]
+pub struct Areas(crate::area::Areas);
+
+impl Areas {
+    /// Fetch area.json from JMA and parse it.
+    pub fn new() -> Result<Areas, ClientError> {
+        block_on(crate::area::Areas::new()).map(Areas)
+    }
+
+    pub fn into_inner(self) -> crate::area::Areas {
+        self.0
+    }
+}
+
+impl Deref for Areas {
+    type Target = crate::area::Areas;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Blocking version of [`crate::forecast_area::ForecastArea`].
+#[derive(Debug)]
+pub struct ForecastArea(crate::forecast_area::ForecastArea);
+
+impl ForecastArea {
+    /// Fetch forecast_area.json.
+    pub fn new() -> Result<ForecastArea, ClientError> {
+        block_on(crate::forecast_area::ForecastArea::new()).map(ForecastArea)
+    }
+
+    pub fn into_inner(self) -> crate::forecast_area::ForecastArea {
+        self.0
+    }
+}
+
+impl Deref for ForecastArea {
+    type Target = crate::forecast_area::ForecastArea;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Blocking version of [`crate::forecast::JmaForecast`].
+pub struct JmaForecast(crate::forecast::JmaForecast);
+
+impl JmaForecast {
+    /// Fetch a forecast JSON in a Office region and store.
+    pub fn new(office: &str) -> Result<JmaForecast, ClientError> {
+        block_on(crate::forecast::JmaForecast::new(office)).map(JmaForecast)
+    }
+
+    /// Fetch the forecast of the area (Class10, Class15 or Class20).
+    pub fn for_area(
+        areas: &crate::area::Areas,
+        area: &crate::area::Area,
+    ) -> Result<Option<AreaForecast>, ClientError> {
+        block_on(crate::forecast::JmaForecast::for_area(areas, area))
+    }
+
+    pub fn into_inner(self) -> crate::forecast::JmaForecast {
+        self.0
+    }
+}
+
+impl Deref for JmaForecast {
+    type Target = crate::forecast::JmaForecast;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::JmaAreaClass;
+
+    #[test]
+    fn runtime() {
+        // Timers and repeated calls work on the shared runtime.
+        for _ in 0..2 {
+            let slept = block_on(async {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                true
+            });
+            assert!(slept);
+        }
+    }
+
+    #[test]
+    fn threads() {
+        use crate::client::{JmaClient, Resource};
+        use std::io::{Read, Write};
+        use std::sync::mpsc;
+        use std::time::{Duration, Instant};
+
+        // A keep-alive server, so the second thread gets the connection opened by the first.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for socket in listener.incoming() {
+                let mut socket = socket.unwrap();
+                std::thread::spawn(move || {
+                    let mut request = [0; 1024];
+                    while matches!(socket.read(&mut request), Ok(n) if n > 0) {
+                        let _ = socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n{}");
+                    }
+                });
+            }
+        });
+
+        let client = JmaClient::new()
+            .timeout(Duration::from_secs(2))
+            .max_retries(0)
+            .min_interval(Duration::ZERO);
+        let (fetched, idle) = mpsc::channel();
+        let (finished, done) = mpsc::channel::<()>();
+        let (client, url) = (&client, &url);
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                let body = block_on(client.get(&format!("{}a", url), Resource::Volatile));
+                assert_eq!(body.unwrap(), b"{}");
+                fetched.send(()).unwrap();
+                // Stay idle while the other thread fetches.
+                let _ = done.recv();
+            });
+            idle.recv().unwrap();
+            let start = Instant::now();
+            let body = block_on(client.get(&format!("{}b", url), Resource::Volatile));
+            finished.send(()).unwrap();
+            assert_eq!(body.unwrap(), b"{}");
+            assert!(start.elapsed() < Duration::from_secs(1));
+        });
+    }
+
+    #[test]
+    fn areas() {
+        let areas = Areas::new().unwrap();
+        let v = areas.values(&JmaAreaClass::Class20, "1020100").unwrap();
+        assert_eq!(v.area.name, "前橋市");
+    }
+}
@@ -0,0 +1,775 @@
+//! # HTTP Client with Cache
+//!
+//! All requests to JMA go through [`JmaClient`].
+//! Responses are cached in memory, and optionally in a directory, with TTLs depending on the resource.
+//!
+//! | Resource | TTL |
+//! | :--- | :--- |
+//! | `area.json`, `forecast_area.json`, `amedastable.json` | 1 day |
+//! | `{office}.json`, overviews | until the next scheduled release (5, 11 and 17 JST) |
+//! | AMeDAS point data | until `latest_time.txt` advances |
+//! | `latest_time.txt` | not cached |
+//!
+//! When an entry has expired, it is revalidated with `If-None-Match` or `If-Modified-Since`
+//! and a `304 Not Modified` response reuses the cached body.
+//! The in-memory cache keeps the most recently stored responses (256 by default).
+//! A failure to write the cache directory is logged and does not fail the request.
+//!
+//! Requests follow the client's [`RequestPolicy`]: each request has a timeout,
+//! transient errors (timeouts, connection errors, 429 and 5xx) are retried with exponential
+//! backoff and jitter, and the number of requests in flight and the interval between them are
+//! limited. The limits are shared by every request made through the same client, so bulk
+//! operations through the default client stay polite.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::area::Areas;
+//! use jma::client::{set_default_client, JmaClient};
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Keep the cache across processes. The environment variable JMA_CACHE_DIR works as well.
+//!     let client = JmaClient::new()
+//!         .cache_dir("/tmp/jma-cache")
+//!         .timeout(Duration::from_secs(10))
+//!         .max_concurrent(2);
+//!     set_default_client(client).ok();
+//!
+//!     // area.json is downloaded once a day.
+//!     let areas = Areas::new().await.unwrap();
+//! }
+//! ```
+
+use crate::forecast::JST_OFFSET_SECONDS;
+use chrono::{DateTime, Duration, FixedOffset, Timelike, Utc};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration as StdDuration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+#[derive(Debug)]
+pub enum ClientError {
+    ReqwestError(reqwest::Error),
+    JsonError(serde_json::Error),
+    IoError(std::io::Error),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::ReqwestError(e) => write!(f, "Reqwest error: {}", e),
+            ClientError::JsonError(e) => write!(f, "JSON error: {}", e),
+            ClientError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> ClientError {
+        ClientError::ReqwestError(err)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(err: serde_json::Error) -> ClientError {
+        ClientError::JsonError(err)
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(err: std::io::Error) -> ClientError {
+        ClientError::IoError(err)
+    }
+}
+
+/// Hours (JST) when the forecasts are released.
+pub const FORECAST_RELEASE_HOURS: [u32; 3] = [5, 11, 17];
+
+/// Kind of the resource, which decides how long the response is cached.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resource {
+    /// Constant tables such as area.json.
+    Constant,
+    /// Forecasts released at the scheduled hours.
+    Forecast,
+    /// AMeDAS data valid while latest_time.txt is the given value.
+    Amedas(String),
+    /// Not cached.
+    Volatile,
+}
+
+/// Returns the next scheduled forecast release after the time.
+pub fn next_forecast_release(now: DateTime<Utc>) -> DateTime<Utc> {
+    let jst = FixedOffset::east_opt(JST_OFFSET_SECONDS).unwrap();
+    let local = now.with_timezone(&jst);
+    let midnight = local
+        .with_hour(0)
+        .and_then(|t| t.with_minute(0))
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap();
+    FORECAST_RELEASE_HOURS
+        .iter()
+        .map(|h| midnight + Duration::hours(*h as i64))
+        .chain([midnight + Duration::hours(24 + FORECAST_RELEASE_HOURS[0] as i64)])
+        .find(|release| *release > local)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct CacheMeta {
+    expires: Option<DateTime<Utc>>,
+    latest_time: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    meta: CacheMeta,
+    body: Vec<u8>,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, resource: &Resource, now: DateTime<Utc>) -> bool {
+        match resource {
+            Resource::Amedas(latest_time) => self.meta.latest_time.as_ref() == Some(latest_time),
+            Resource::Volatile => false,
+            _ => self.meta.expires.is_some_and(|expires| now < expires),
+        }
+    }
+}
+
+/// Timeout, retry and rate limit of the requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestPolicy {
+    /// Timeout of each attempt, including reading the body.
+    pub timeout: StdDuration,
+    /// Number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry. It doubles on every retry.
+    pub base_delay: StdDuration,
+    /// Upper bound of the delay between retries.
+    pub max_delay: StdDuration,
+    /// Number of requests in flight at the same time.
+    pub max_concurrent: usize,
+    /// Minimum interval between the starts of two requests.
+    pub min_interval: StdDuration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        RequestPolicy {
+            timeout: StdDuration::from_secs(30),
+            max_retries: 3,
+            base_delay: StdDuration::from_millis(500),
+            max_delay: StdDuration::from_secs(10),
+            max_concurrent: 4,
+            min_interval: StdDuration::from_millis(100),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// Delay before the retry following the attempt (0 for the first attempt).
+    ///
+    /// `jitter` in [0, 1) scales the exponential delay into [delay / 2, delay).
+    pub fn backoff(&self, attempt: u32, jitter: f64) -> StdDuration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        delay.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+/// Returns true if the request may succeed when it is retried.
+pub fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn is_transient_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect()
+}
+
+/// Pseudo-random number in [0, 1).
+fn jitter() -> f64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Seconds in the Retry-After header.
+fn retry_after(response: &reqwest::Response) -> Option<StdDuration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(StdDuration::from_secs)
+}
+
+/// Responses cached in memory, with the order they were stored.
+#[derive(Debug, Default)]
+struct MemoryCache {
+    entries: HashMap<String, (u64, CacheEntry)>,
+    sequence: u64,
+}
+
+impl MemoryCache {
+    fn get(&self, url: &str) -> Option<&CacheEntry> {
+        self.entries.get(url).map(|(_, entry)| entry)
+    }
+
+    /// Insert the entry, dropping the oldest ones beyond the capacity.
+    fn insert(&mut self, url: &str, entry: CacheEntry, capacity: usize) {
+        self.sequence += 1;
+        self.entries.insert(url.to_string(), (self.sequence, entry));
+        while self.entries.len() > capacity.max(1) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (sequence, _))| *sequence)
+                .map(|(url, _)| url.clone());
+            match oldest {
+                Some(url) => self.entries.remove(&url),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Default number of responses cached in memory.
+pub const DEFAULT_MEMORY_CAPACITY: usize = 256;
+
+/// HTTP client for JMA.
+#[derive(Debug)]
+pub struct JmaClient {
+    cache_dir: Option<PathBuf>,
+    constant_ttl: Duration,
+    memory: Mutex<MemoryCache>,
+    memory_capacity: usize,
+    /// Shared by every request to reuse the connections.
+    http: reqwest::Client,
+    policy: RequestPolicy,
+    limiter: Semaphore,
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl Default for JmaClient {
+    fn default() -> Self {
+        JmaClient::new()
+    }
+}
+
+impl JmaClient {
+    /// Create a client with the in-memory cache.
+    pub fn new() -> Self {
+        JmaClient {
+            cache_dir: None,
+            constant_ttl: Duration::days(1),
+            memory: Mutex::new(MemoryCache::default()),
+            memory_capacity: DEFAULT_MEMORY_CAPACITY,
+            http: reqwest::Client::new(),
+            policy: RequestPolicy::default(),
+            limiter: Semaphore::new(RequestPolicy::default().max_concurrent),
+            last_request: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Also cache the responses in the directory.
+    pub fn cache_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// TTL of the constant tables. The default is one day.
+    pub fn constant_ttl(mut self, ttl: Duration) -> Self {
+        self.constant_ttl = ttl;
+        self
+    }
+
+    /// Number of responses cached in memory. The oldest one is dropped first.
+    pub fn memory_capacity(mut self, capacity: usize) -> Self {
+        self.memory_capacity = capacity;
+        self
+    }
+
+    /// Replace the request policy.
+    pub fn request_policy(mut self, policy: RequestPolicy) -> Self {
+        self.limiter = Semaphore::new(policy.max_concurrent.max(1));
+        self.policy = policy;
+        self
+    }
+
+    /// Timeout of each request. The default is 30 seconds.
+    pub fn timeout(mut self, timeout: StdDuration) -> Self {
+        self.policy.timeout = timeout;
+        self
+    }
+
+    /// Number of retries for transient errors. The default is 3; 0 disables retrying.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.policy.max_retries = max_retries;
+        self
+    }
+
+    /// Delay before the first retry and the upper bound of the delay.
+    /// The defaults are 500 milliseconds and 10 seconds.
+    pub fn backoff(mut self, base_delay: StdDuration, max_delay: StdDuration) -> Self {
+        self.policy.base_delay = base_delay;
+        self.policy.max_delay = max_delay;
+        self
+    }
+
+    /// Number of requests in flight at the same time. The default is 4.
+    pub fn max_concurrent(self, max_concurrent: usize) -> Self {
+        let policy = RequestPolicy {
+            max_concurrent,
+            ..self.policy.clone()
+        };
+        self.request_policy(policy)
+    }
+
+    /// Minimum interval between the starts of two requests. The default is 100 milliseconds.
+    pub fn min_interval(mut self, min_interval: StdDuration) -> Self {
+        self.policy.min_interval = min_interval;
+        self
+    }
+
+    /// Returns the request policy.
+    pub fn policy(&self) -> &RequestPolicy {
+        &self.policy
+    }
+
+    /// Clear the in-memory cache.
+    pub fn clear(&self) {
+        self.memory.lock().unwrap().entries.clear();
+    }
+
+    /// Fetch the URL and deserialize the JSON.
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        resource: Resource,
+    ) -> Result<T, ClientError> {
+        let body = self.get(url, resource).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Fetch the URL as text.
+    pub async fn get_text(&self, url: &str, resource: Resource) -> Result<String, ClientError> {
+        let body = self.get(url, resource).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Fetch the URL, or returns the cached body.
+    pub async fn get(&self, url: &str, resource: Resource) -> Result<Vec<u8>, ClientError> {
+        let now = Utc::now();
+        let cached = self.lookup(url);
+        if let Some(entry) = &cached
+            && entry.is_fresh(&resource, now)
+        {
+            return Ok(entry.body.clone());
+        }
+
+        // The permit is held until the body has been read.
+        let (response, permit) = self.send(url, cached.as_ref()).await?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let mut etag = header(ETAG);
+        let mut last_modified = header(LAST_MODIFIED);
+        let not_modified = response.status() == StatusCode::NOT_MODIFIED;
+        let body = match (not_modified, cached) {
+            (true, Some(entry)) => {
+                // A 304 response may omit the validators.
+                etag = etag.or(entry.meta.etag);
+                last_modified = last_modified.or(entry.meta.last_modified);
+                entry.body
+            }
+            _ => response.error_for_status()?.bytes().await?.to_vec(),
+        };
+        drop(permit);
+
+        if resource != Resource::Volatile {
+            let meta = CacheMeta {
+                expires: self.expires(&resource, now),
+                latest_time: match &resource {
+                    Resource::Amedas(latest_time) => Some(latest_time.clone()),
+                    _ => None,
+                },
+                etag,
+                last_modified,
+            };
+            let entry = CacheEntry {
+                meta,
+                body: body.clone(),
+            };
+            // The body has been fetched, so a broken cache directory only costs a later request.
+            if let Err(e) = self.store(url, entry) {
+                log::warn!("failed to cache {}: {}", url, e);
+            }
+        }
+        Ok(body)
+    }
+
+    /// Send the request, retrying transient errors.
+    ///
+    /// Returns the response with the permit of the concurrency limit.
+    async fn send(
+        &self,
+        url: &str,
+        cached: Option<&CacheEntry>,
+    ) -> Result<(reqwest::Response, SemaphorePermit<'_>), reqwest::Error> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.http.get(url).timeout(self.policy.timeout);
+            if let Some(entry) = cached {
+                if let Some(etag) = &entry.meta.etag {
+                    request = request.header(IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = &entry.meta.last_modified {
+                    request = request.header(IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+            // The semaphore is never closed.
+            let permit = self.limiter.acquire().await.unwrap();
+            self.throttle().await;
+            let result = request.send().await;
+
+            let retry = match &result {
+                Ok(response) if is_transient_status(response.status()) => {
+                    Some(retry_after(response))
+                }
+                Err(e) if is_transient_error(e) => Some(None),
+                _ => None,
+            };
+            match retry {
+                Some(retry_after) if attempt < self.policy.max_retries => {
+                    let backoff = self.policy.backoff(attempt, jitter());
+                    let delay = match retry_after {
+                        Some(retry_after) => retry_after.min(self.policy.max_delay).max(backoff),
+                        None => backoff,
+                    };
+                    match &result {
+                        Ok(response) => log::warn!(
+                            "{} returned {}, retrying in {:?}",
+                            url,
+                            response.status(),
+                            delay
+                        ),
+                        Err(e) => log::warn!("{}: {}, retrying in {:?}", url, e, delay),
+                    }
+                    drop(permit);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => return result.map(|response| (response, permit)),
+            }
+        }
+    }
+
+    /// Wait until the minimum interval has passed since the last request.
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            tokio::time::sleep_until(last + self.policy.min_interval).await;
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    fn expires(&self, resource: &Resource, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match resource {
+            Resource::Constant => Some(now + self.constant_ttl),
+            Resource::Forecast => Some(next_forecast_release(now)),
+            _ => None,
+        }
+    }
+
+    fn cache_path(&self, url: &str) -> Option<(PathBuf, PathBuf)> {
+        let name = cache_name(url);
+        self.cache_dir.as_ref().map(|dir| {
+            (
+                dir.join(format!("{}.body", name)),
+                dir.join(format!("{}.meta", name)),
+            )
+        })
+    }
+
+    fn lookup(&self, url: &str) -> Option<CacheEntry> {
+        if let Some(entry) = self.memory.lock().unwrap().get(url) {
+            return Some(entry.clone());
+        }
+        let (body_path, meta_path) = self.cache_path(url)?;
+        let meta: CacheMeta = serde_json::from_slice(&fs::read(meta_path).ok()?).ok()?;
+        let body = fs::read(body_path).ok()?;
+        let entry = CacheEntry { meta, body };
+        self.memory
+            .lock()
+            .unwrap()
+            .insert(url, entry.clone(), self.memory_capacity);
+        Some(entry)
+    }
+
+    /// Cache the entry in memory, then in the directory.
+    fn store(&self, url: &str, entry: CacheEntry) -> Result<(), ClientError> {
+        let paths = self.cache_path(url);
+        let disk = entry.clone();
+        self.memory
+            .lock()
+            .unwrap()
+            .insert(url, entry, self.memory_capacity);
+        if let Some((body_path, meta_path)) = paths {
+            if let Some(dir) = body_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(body_path, &disk.body)?;
+            fs::write(meta_path, serde_json::to_vec(&disk.meta)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// File name of the cached URL.
+///
+/// Characters other than ASCII alphanumerics, '.' and '-' are percent-encoded,
+/// so different URLs never share a file.
+fn cache_name(url: &str) -> String {
+    let mut name = String::new();
+    for byte in url.trim_start_matches("https://").bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'.' || byte == b'-' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    name
+}
+
+static DEFAULT_CLIENT: OnceLock<JmaClient> = OnceLock::new();
+
+/// Returns the client used by the constructors such as `Areas::new()`.
+///
+/// The cache directory is taken from the environment variable `JMA_CACHE_DIR` if it is set.
+pub fn default_client() -> &'static JmaClient {
+    DEFAULT_CLIENT.get_or_init(|| match std::env::var_os("JMA_CACHE_DIR") {
+        Some(dir) => JmaClient::new().cache_dir(dir),
+        None => JmaClient::new(),
+    })
+}
+
+/// Replace the default client. Fails if the default client is already in use.
+pub fn set_default_client(client: JmaClient) -> Result<(), Box<JmaClient>> {
+    DEFAULT_CLIENT.set(client).map_err(Box::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(datetime: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(datetime)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_release() {
+        let next = next_forecast_release(utc("2025-03-28T04:59:00+09:00"));
+        assert_eq!(next, utc("2025-03-28T05:00:00+09:00"));
+        let next = next_forecast_release(utc("2025-03-28T11:00:00+09:00"));
+        assert_eq!(next, utc("2025-03-28T17:00:00+09:00"));
+        // 2025-03-28T20:00:00+09:00
+        let next = next_forecast_release(utc("2025-03-28T11:00:00Z"));
+        assert_eq!(next, utc("2025-03-29T05:00:00+09:00"));
+    }
+
+    #[test]
+    fn freshness() {
+        let now = utc("2025-03-28T12:00:00+09:00");
+        let client = JmaClient::new();
+        let entry = CacheEntry {
+            meta: CacheMeta {
+                expires: client.expires(&Resource::Forecast, now),
+                latest_time: Some("2025-03-28T11:50:00+09:00".to_string()),
+                etag: None,
+                last_modified: None,
+            },
+            body: Vec::new(),
+        };
+        assert!(entry.is_fresh(&Resource::Forecast, utc("2025-03-28T16:59:00+09:00")));
+        assert!(!entry.is_fresh(&Resource::Forecast, utc("2025-03-28T17:00:00+09:00")));
+        assert!(entry.is_fresh(
+            &Resource::Amedas("2025-03-28T11:50:00+09:00".to_string()),
+            now
+        ));
+        assert!(!entry.is_fresh(
+            &Resource::Amedas("2025-03-28T12:00:00+09:00".to_string()),
+            now
+        ));
+        assert!(!entry.is_fresh(&Resource::Volatile, now));
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RequestPolicy::default();
+        assert_eq!(policy.backoff(0, 0.0), StdDuration::from_millis(250));
+        assert_eq!(policy.backoff(0, 1.0), StdDuration::from_millis(500));
+        assert_eq!(policy.backoff(2, 1.0), StdDuration::from_secs(2));
+        // Capped by max_delay
+        assert_eq!(policy.backoff(10, 1.0), StdDuration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX, 0.0), StdDuration::from_secs(5));
+        for _ in 0..100 {
+            let j = jitter();
+            assert!((0.0..1.0).contains(&j));
+        }
+    }
+
+    #[test]
+    fn transient_status() {
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(StatusCode::NOT_MODIFIED));
+    }
+
+    #[tokio::test]
+    async fn throttle() {
+        let client = JmaClient::new().min_interval(StdDuration::from_millis(50));
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.throttle().await;
+        }
+        assert!(start.elapsed() >= StdDuration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn disk_cache() {
+        let dir = std::env::temp_dir().join(format!("jma-cache-test-{}", std::process::id()));
+        let url = "https://www.jma.go.jp/bosai/common/const/area.json";
+        let writer = JmaClient::new().cache_dir(&dir);
+        let entry = CacheEntry {
+            meta: CacheMeta {
+                expires: Some(Utc::now() + Duration::hours(1)),
+                latest_time: None,
+                etag: Some("\"abc\"".to_string()),
+                last_modified: None,
+            },
+            body: b"{\"cached\": true}".to_vec(),
+        };
+        writer.store(url, entry).unwrap();
+
+        // A new client (e.g. after a cold start) reads the directory without the network.
+        let reader = JmaClient::new().cache_dir(&dir);
+        let value: serde_json::Value = reader.get_json(url, Resource::Constant).await.unwrap();
+        assert_eq!(value["cached"], true);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn permit_covers_body() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A server sending the headers at once and the body 200 milliseconds later.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut request = [0; 1024];
+                    let _ = socket.read(&mut request).await;
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n")
+                        .await;
+                    tokio::time::sleep(StdDuration::from_millis(200)).await;
+                    let _ = socket.write_all(b"{}").await;
+                });
+            }
+        });
+
+        let client = JmaClient::new()
+            .max_concurrent(1)
+            .min_interval(StdDuration::ZERO);
+        let start = Instant::now();
+        let (a, b) = tokio::join!(
+            client.get(&url, Resource::Volatile),
+            client.get(&url, Resource::Volatile)
+        );
+        assert_eq!(a.unwrap(), b"{}");
+        assert_eq!(b.unwrap(), b"{}");
+        assert!(start.elapsed() >= StdDuration::from_millis(400));
+    }
+
+    #[test]
+    fn cache_names() {
+        assert_eq!(
+            cache_name("https://www.jma.go.jp/bosai/common/const/area.json"),
+            "www.jma.go.jp%2Fbosai%2Fcommon%2Fconst%2Farea.json"
+        );
+        assert_ne!(cache_name("https://a/b_c"), cache_name("https://a/b/c"));
+        assert_ne!(cache_name("https://a/b?c"), cache_name("https://a/b/c"));
+        assert_ne!(cache_name("https://a/%2F"), cache_name("https://a//"));
+    }
+
+    fn entry(body: &[u8]) -> CacheEntry {
+        CacheEntry {
+            meta: CacheMeta {
+                expires: None,
+                latest_time: None,
+                etag: None,
+                last_modified: None,
+            },
+            body: body.to_vec(),
+        }
+    }
+
+    #[test]
+    fn memory_capacity() {
+        let client = JmaClient::new().memory_capacity(2);
+        client.store("https://a/1", entry(b"1")).unwrap();
+        client.store("https://a/2", entry(b"2")).unwrap();
+        client.store("https://a/3", entry(b"3")).unwrap();
+        assert!(client.lookup("https://a/1").is_none());
+        assert_eq!(client.lookup("https://a/3").unwrap().body, b"3");
+        assert_eq!(client.memory.lock().unwrap().entries.len(), 2);
+    }
+
+    #[test]
+    fn broken_cache_dir() {
+        // A file where the directory should be.
+        let file = std::env::temp_dir().join(format!("jma-cache-file-{}", std::process::id()));
+        fs::write(&file, b"").unwrap();
+        let client = JmaClient::new().cache_dir(file.join("cache"));
+        assert!(client.store("https://a/1", entry(b"1")).is_err());
+        // Still cached in memory.
+        assert_eq!(client.lookup("https://a/1").unwrap().body, b"1");
+        fs::remove_file(file).unwrap();
+    }
+}
@@ -138,9 +138,10 @@
 //! ```
 //! 
 //! ## Example
-//! ```rust
+//! ```rust,no_run
 //! use jma::forecast::JmaForecast;
 //! 
+//! # #[cfg(feature = "http")]
 //! #[tokio::main]
 //! async fn main() {
 //!     let sapporo = ("016000", "14163");
@@ -151,47 +152,63 @@
 //!     println!("report_datetime: {}", peak.report_datetime);
 //!     println!("      area_name: {}", peak.area_name);
 //!     println!("      area_code: {}", peak.area_code);
-//!     println!("         lowest: {}", peak.lowest);
-//!     println!("lowest_datetime: {}", peak.lowest_datetime);
-//!     println!("        highest: {}", peak.highest);
-//!     println!("highest_datetime: {}", peak.highest_datetime);
+//!     for day in [&peak.today, &peak.tomorrow] {
+//!         if let Some(lowest) = &day.lowest {
+//!             println!("{} lowest: {} ({})", day.date, lowest.celsius, lowest.datetime);
+//!         }
+//!         if let Some(highest) = &day.highest {
+//!             println!("{} highest: {} ({})", day.date, highest.celsius, highest.datetime);
+//!         }
+//!     }
 //! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
 //! ```
 //! Output:
 //! ```console
 //! report_datetime: 2025-11-18T11:00:00+09:00
 //!       area_name: 札幌
 //!       area_code: 14163
-//!          lowest: -1
-//! lowest_datetime: 2025-11-19T00:00:00+09:00
-//!         highest: 2
-//! highest_datetime: 2025-11-18T09:00:00+09:0
+//! 2025-11-18 highest: 2 (2025-11-18T09:00:00+09:00)
+//! 2025-11-19 lowest: -1 (2025-11-19T00:00:00+09:00)
+//! 2025-11-19 highest: 3 (2025-11-19T09:00:00+09:00)
 //! ```
 
-use chrono::{Local, Timelike};
-use reqwest::Error;
+use crate::area::{Area, Areas, JmaAreaClass};
+#[cfg(feature = "http")]
+use crate::client::{ClientError, Resource, default_client};
+use crate::forecast_area::ForecastArea;
+use crate::forecast_text::{
+    WaveForecast, WeatherText, WindForecast, parse_wave, parse_weather, parse_wind,
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Timelike};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+
+/// UTC offset of JST.
+pub(crate) const JST_OFFSET_SECONDS: i32 = 9 * 3600;
 
 ///
-/// When accessing Office code 140030 or 460040, 404 Not Found is returned.
-/// On the JMA website, 140100 or 460100 is used.
+/// When accessing Office code 014030 or 460040, 404 Not Found is returned.
+/// On the JMA website, 014100 or 460100 is used.
 ///
-fn office_for_url(offices: &str) -> &str {
+pub(crate) fn office_for_url(offices: &str) -> &str {
     let data = [
         ("014030", "014100"), // 北海道地方 / 十勝地方
         ("460040", "460100"), // 九州南部・奄美地方 / 奄美地方
     ];
 
-    let mut result = offices;
-    for (key, value) in &data {
-        if offices.to_string() == key.to_string() {
-            result = value;
-            break;
-        }
-    }
+    data.iter()
+        .find(|(key, _)| *key == offices)
+        .map_or(offices, |(_, value)| value)
+}
 
-    result
+/// A report of {office}.json, checked for the shape only.
+#[derive(Deserialize)]
+struct ForecastReport {
+    #[serde(rename = "timeSeries")]
+    _time_series: Vec<Value>,
 }
 
 /// Store fetched a forecast from JMA site.
@@ -201,16 +218,98 @@ pub struct JmaForecast {
 
 impl JmaForecast {
     /// Fetch a forecast JSON in a Office region and store.
-    pub async fn new(office: &str) -> Result<JmaForecast, Error> {
+    #[cfg(feature = "http")]
+    pub async fn new(office: &str) -> Result<JmaForecast, ClientError> {
         let url_office = office_for_url(office);
         let url = format!(
             "https://www.jma.go.jp/bosai/forecast/data/forecast/{}.json",
             url_office
         );
-        let json = reqwest::get(&url).await?.json::<Value>().await?;
+        let json = default_client().get_json(&url, Resource::Forecast).await?;
+        Ok(JmaForecast::from_value(json)?)
+    }
+
+    /// Parse a forecast JSON ({office}.json).
+    pub fn from_json_str(json: &str) -> Result<JmaForecast, serde_json::Error> {
+        JmaForecast::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Parse a forecast JSON ({office}.json) from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<JmaForecast, serde_json::Error> {
+        JmaForecast::from_value(serde_json::from_reader(reader)?)
+    }
+
+    /// Store a parsed forecast JSON ({office}.json).
+    ///
+    /// The JSON must be an array of reports with `timeSeries`, the short-term report first.
+    pub fn from_value(json: Value) -> Result<JmaForecast, serde_json::Error> {
+        let reports = Vec::<ForecastReport>::deserialize(&json)?;
+        if reports.is_empty() {
+            return Err(serde::de::Error::invalid_length(
+                0,
+                &"the short-term report",
+            ));
+        }
         Ok(JmaForecast { json })
     }
 
+    /// Fetch the forecast of the area (Class10, Class15 or Class20).
+    ///
+    /// Returns None if the area has no Class10 ancestor or the forecast does not cover it.
+    #[cfg(feature = "http")]
+    pub async fn for_area(areas: &Areas, area: &Area) -> Result<Option<AreaForecast>, ClientError> {
+        let location = match ForecastLocation::resolve(areas, area) {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        let forecast = JmaForecast::new(&location.forecast_office).await?;
+        let forecast_area = ForecastArea::new().await?;
+        Ok(forecast.area_forecast(&location, Some(&forecast_area)))
+    }
+
+    /// Returns the forecast of the location.
+    ///
+    /// The weekly forecast is of the only weekly region,
+    /// or of the weekly region with the code of the Class10.
+    /// Its temperatures are of the first AMeDAS station of the Class10 in forecast_area.json
+    /// that is a weekly temperature point, or of the only temperature point.
+    pub fn area_forecast(
+        &self,
+        location: &ForecastLocation,
+        forecast_area: Option<&ForecastArea>,
+    ) -> Option<AreaForecast> {
+        let short_term = self.short_term(&location.class10.code)?;
+        let regions = self.weekly_areas();
+        let region = match regions.as_slice() {
+            [only] => Some(only),
+            _ => regions.iter().find(|r| r.code == location.class10.code),
+        };
+        let points = self.weekly_temp_points();
+        let stations = forecast_area
+            .and_then(|f| f.get_amedas_by_class10(&location.class10.code))
+            .cloned()
+            .unwrap_or_default();
+        let temp_point = stations
+            .iter()
+            .find(|station| points.iter().any(|p| p.code == **station))
+            .map(|station| station.as_str())
+            .or(match points.as_slice() {
+                [only] => Some(only.code.as_str()),
+                _ => None,
+            });
+        let weekly = region.and_then(|r| self.weekly(&r.code, temp_point));
+        Some(AreaForecast {
+            location: location.clone(),
+            short_term,
+            weekly,
+        })
+    }
+
+    /// Returns the fetched forecast JSON.
+    pub fn json(&self) -> &Value {
+        &self.json
+    }
+
     /// Convert the old name used for a weather forecast region to the current city name.
     pub fn find_newcity(oldcity: &str) -> Option<String> {
         let data = [
@@ -287,81 +386,406 @@ impl JmaForecast {
         result
     }
 
-    /// Get temperature points of the class10 regions.
-    pub fn get_temperature_points(&self) -> Vec<Temps> {
-        serde_json::from_value(self.json[0]["timeSeries"][2]["areas"].clone()).unwrap()
+    /// Get the report datetime of the short-term forecast.
+    fn report_datetime(&self) -> Option<String> {
+        self.json[0]["reportDatetime"]
+            .as_str()
+            .map(|s| s.to_string())
     }
 
-    pub fn temperature_forecast(&self, area_code: &str) -> Option<PeakTemp> {
-	let peaks: PeakTemps = match serde_json::from_value(self.json[0]["timeSeries"][2].clone()) {
-	    Ok(peaks) => peaks,
-	    Err(_) => return None,
-	};
-
-        let mut report_dt = None;
-        if let Value::Array(arr) = &self.json {
-            if let Some(first_object) = arr.get(0) {
-                if let Some(report_datetime_value) = first_object.get("reportDatetime") {
-                    if let Value::String(report_datetime_str) = report_datetime_value {
-                        report_dt = Some(report_datetime_str);
+    /// Get the short-term forecast of the class10 region.
+    ///
+    /// Returns the weather, wind and wave for today, tomorrow and the day after tomorrow,
+    /// and the 6-hour probabilities of precipitation.
+    pub fn short_term(&self, class10_code: &str) -> Option<ShortTermForecast> {
+        let report_datetime = self.report_datetime()?;
+        let weathers: TimeSeries<WeatherArea> =
+            serde_json::from_value(self.json[0]["timeSeries"][0].clone()).ok()?;
+        let pops: TimeSeries<PopArea> =
+            serde_json::from_value(self.json[0]["timeSeries"][1].clone()).ok()?;
+
+        let weather = weathers
+            .areas
+            .iter()
+            .find(|a| a.area.code == class10_code)?;
+        let days = weathers
+            .time_defines
+            .iter()
+            .enumerate()
+            .map(|(i, datetime)| DailyWeather {
+                datetime: datetime.clone(),
+                weather_code: weather.weather_codes.get(i).cloned().unwrap_or_default(),
+                weather: weather.weathers.get(i).cloned().unwrap_or_default(),
+                wind: weather.winds.get(i).cloned().unwrap_or_default(),
+                wave: weather.waves.get(i).cloned(),
+            })
+            .collect();
+
+        let pops = match pops.areas.iter().find(|a| a.area.code == class10_code) {
+            Some(area) => pops
+                .time_defines
+                .iter()
+                .zip(area.pops.iter())
+                .map(|(datetime, pop)| Pop {
+                    datetime: datetime.clone(),
+                    pop: pop.parse().ok(),
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Some(ShortTermForecast {
+            report_datetime,
+            area_name: weather.area.name.clone(),
+            area_code: weather.area.code.clone(),
+            days,
+            pops,
+        })
+    }
+
+    /// Get the weekly forecast of the region.
+    ///
+    /// `area_code` is a code of the weekly forecast region (e.g. "020100").
+    /// `temp_point` is the temperature point (e.g. "31312") of the temperatures and the normals;
+    /// a region may have several points, so it is not guessed here (see [`JmaForecast::area_forecast`]).
+    pub fn weekly(&self, area_code: &str, temp_point: Option<&str>) -> Option<WeeklyForecast> {
+        let report_datetime = self.json[1]["reportDatetime"].as_str()?.to_string();
+        let weathers: TimeSeries<WeeklyWeatherArea> =
+            serde_json::from_value(self.json[1]["timeSeries"][0].clone()).ok()?;
+        let temps = self.weekly_temps()?;
+        let temp_average: Averages =
+            serde_json::from_value(self.json[1]["tempAverage"].clone()).unwrap_or_default();
+        let precip_average: Averages =
+            serde_json::from_value(self.json[1]["precipAverage"].clone()).unwrap_or_default();
+
+        let weather = weathers.areas.iter().find(|a| a.area.code == area_code)?;
+        let temp = temp_point.and_then(|point| temps.areas.iter().find(|a| a.area.code == point));
+        let normal_temp = temp
+            .and_then(|t| {
+                temp_average
+                    .areas
+                    .iter()
+                    .find(|a| a.area.code == t.area.code)
+            })
+            .and_then(NormalRange::from_average);
+        let normal_precip = temp
+            .and_then(|t| {
+                precip_average
+                    .areas
+                    .iter()
+                    .find(|a| a.area.code == t.area.code)
+            })
+            .and_then(NormalRange::from_average);
+
+        let days = weathers
+            .time_defines
+            .iter()
+            .enumerate()
+            .map(|(i, datetime)| {
+                let (temp_min, temp_max) = match temp {
+                    Some(t) => (t.min(i), t.max(i)),
+                    None => (None, None),
+                };
+                let temp_min_normal = match (&temp_min, &normal_temp) {
+                    (Some(t), Some(n)) => Some(NormalComparison::compare(t.value, n.min)),
+                    _ => None,
+                };
+                let temp_max_normal = match (&temp_max, &normal_temp) {
+                    (Some(t), Some(n)) => Some(NormalComparison::compare(t.value, n.max)),
+                    _ => None,
+                };
+                WeeklyDay {
+                    datetime: datetime.clone(),
+                    weather_code: weather.weather_codes.get(i).cloned().unwrap_or_default(),
+                    pop: weather.pops.get(i).and_then(|p| p.parse().ok()),
+                    reliability: weather
+                        .reliabilities
+                        .get(i)
+                        .and_then(|r| Reliability::to_reliability(r)),
+                    temp_min,
+                    temp_max,
+                    temp_min_normal,
+                    temp_max_normal,
+                }
+            })
+            .collect();
+
+        Some(WeeklyForecast {
+            report_datetime,
+            area_name: weather.area.name.clone(),
+            area_code: weather.area.code.clone(),
+            temp_area_name: temp.map(|t| t.area.name.clone()),
+            temp_area_code: temp.map(|t| t.area.code.clone()),
+            days,
+            normal_temp,
+            normal_precip,
+        })
+    }
+
+    /// Get the class10 regions of the short-term forecast.
+    pub fn short_term_areas(&self) -> Vec<ForecastAreaCode> {
+        match serde_json::from_value::<TimeSeries<WeatherArea>>(
+            self.json[0]["timeSeries"][0].clone(),
+        ) {
+            Ok(series) => series.areas.into_iter().map(|a| a.area).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Get the regions of the weekly forecast.
+    pub fn weekly_areas(&self) -> Vec<ForecastAreaCode> {
+        match serde_json::from_value::<TimeSeries<WeeklyWeatherArea>>(
+            self.json[1]["timeSeries"][0].clone(),
+        ) {
+            Ok(series) => series.areas.into_iter().map(|a| a.area).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Get the temperature points of the weekly forecast.
+    pub fn weekly_temp_points(&self) -> Vec<TempsArea> {
+        match self.weekly_temps() {
+            Some(series) => series.areas.into_iter().map(|a| a.area).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the weekly temperatures of the temperature points.
+    fn weekly_temps(&self) -> Option<TimeSeries<WeeklyTempArea>> {
+        serde_json::from_value(self.json[1]["timeSeries"][1].clone()).ok()
+    }
+
+    /// Compare with the previous release and returns what changed.
+    ///
+    /// Only the values present in both releases for the same datetime are compared.
+    pub fn diff(&self, previous: &JmaForecast) -> ForecastDiff {
+        let mut changes = Vec::new();
+
+        for area in self.short_term_areas() {
+            let (new, old) = match (self.short_term(&area.code), previous.short_term(&area.code)) {
+                (Some(new), Some(old)) => (new, old),
+                _ => continue,
+            };
+            for day in &new.days {
+                if let Some(old_day) = old.days.iter().find(|d| d.datetime == day.datetime) {
+                    Change::push(
+                        &mut changes,
+                        &area,
+                        ForecastField::WeatherCode,
+                        &day.datetime,
+                        Some(old_day.weather_code.clone()),
+                        Some(day.weather_code.clone()),
+                    );
+                }
+            }
+            for pop in &new.pops {
+                if let Some(old_pop) = old.pops.iter().find(|p| p.datetime == pop.datetime) {
+                    Change::push_number(
+                        &mut changes,
+                        &area,
+                        ForecastField::Pop,
+                        &pop.datetime,
+                        old_pop.pop.map(|p| p as f32),
+                        pop.pop.map(|p| p as f32),
+                    );
+                }
+            }
+        }
+
+        if let (Ok(new), Ok(old)) = (
+            serde_json::from_value::<PeakTemps>(self.json[0]["timeSeries"][2].clone()),
+            serde_json::from_value::<PeakTemps>(previous.json[0]["timeSeries"][2].clone()),
+        ) {
+            for area in &new.areas {
+                let old_area = match old.areas.iter().find(|a| a.area.code == area.area.code) {
+                    Some(a) => a,
+                    None => continue,
+                };
+                let code = ForecastAreaCode {
+                    name: area.area.name.clone(),
+                    code: area.area.code.clone(),
+                };
+                for (datetime, temp) in new.time_defines.iter().zip(area.temps.iter()) {
+                    // The first value with the same datetime; later ones only repeat another value.
+                    let old_temp = old
+                        .time_defines
+                        .iter()
+                        .position(|d| d == datetime)
+                        .and_then(|i| old_area.temps.get(i));
+                    if let Some(old_temp) = old_temp {
+                        Change::push_number(
+                            &mut changes,
+                            &code,
+                            ForecastField::Temp,
+                            datetime,
+                            old_temp.parse().ok(),
+                            temp.parse().ok(),
+                        );
                     }
                 }
             }
         }
 
-	let report_datetime = match report_dt {
-	    Some(dt) => dt.to_string(),
-	    None => return None,
-	};
+        for area in self.weekly_areas() {
+            let (new, old) = match (
+                self.weekly(&area.code, None),
+                previous.weekly(&area.code, None),
+            ) {
+                (Some(new), Some(old)) => (new, old),
+                _ => continue,
+            };
+            for day in &new.days {
+                let old_day = match old.days.iter().find(|d| d.datetime == day.datetime) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                Change::push(
+                    &mut changes,
+                    &area,
+                    ForecastField::WeeklyWeatherCode,
+                    &day.datetime,
+                    Some(old_day.weather_code.clone()),
+                    Some(day.weather_code.clone()),
+                );
+                Change::push_number(
+                    &mut changes,
+                    &area,
+                    ForecastField::WeeklyPop,
+                    &day.datetime,
+                    old_day.pop.map(|p| p as f32),
+                    day.pop.map(|p| p as f32),
+                );
+            }
+        }
 
-        let now = Local::now();
-        let (lowest_index, highest_index) = if 5 <= now.hour() && now.hour() < 17 {
-	    (2, 0)
+        // The weekly temperatures are compared by the temperature point.
+        if let (Some(new), Some(old)) = (self.weekly_temps(), previous.weekly_temps()) {
+            for temp in &new.areas {
+                let old_temp = match old.areas.iter().find(|a| a.area.code == temp.area.code) {
+                    Some(t) => t,
+                    None => continue,
+                };
+                let area = ForecastAreaCode {
+                    name: temp.area.name.clone(),
+                    code: temp.area.code.clone(),
+                };
+                for (i, datetime) in new.time_defines.iter().enumerate() {
+                    let old_i = match old.time_defines.iter().position(|d| d == datetime) {
+                        Some(i) => i,
+                        None => continue,
+                    };
+                    Change::push_number(
+                        &mut changes,
+                        &area,
+                        ForecastField::WeeklyTempMin,
+                        datetime,
+                        old_temp.min(old_i).map(|t| t.value),
+                        temp.min(i).map(|t| t.value),
+                    );
+                    Change::push_number(
+                        &mut changes,
+                        &area,
+                        ForecastField::WeeklyTempMax,
+                        datetime,
+                        old_temp.max(old_i).map(|t| t.value),
+                        temp.max(i).map(|t| t.value),
+                    );
+                }
+            }
         }
-        else {
-	    (0, 1)
-        };
-	let lowest_datetime = match peaks.time_defines.get(lowest_index) {
-	    Some(dt) => dt.clone(),
-	    None => return None,
-	};
-	let highest_datetime = match peaks.time_defines.get(highest_index) {
-	    Some(dt) => dt.clone(),
-	    None => return None,
-	};
-	let mut peak = PeakTemp {
-	    report_datetime,
-	    area_name: "".to_string(),
-	    area_code: "".to_string(),
-	    lowest: "".to_string(),
-	    lowest_datetime,
-	    highest: "".to_string(),
-	    highest_datetime,
-	};
-	match peaks.areas.iter().find(|a| a.area.code == area_code) {
-	    Some(area) => {
-		peak.area_name = area.area.name.to_string();
-		peak.area_code = area.area.code.to_string();
-		peak.lowest = match area.temps.get(lowest_index) {
-		    Some(l) => l.to_string(),
-		    None => return None,
-		};
-		peak.highest = match area.temps.get(highest_index) {
-		    Some(h) => h.to_string(),
-		    None => return None,
-		};
-	    },
-	    None => return None,
-	};
-
-        return Some(peak);
+
+        ForecastDiff {
+            previous_report_datetime: previous.report_datetime().unwrap_or_default(),
+            report_datetime: self.report_datetime().unwrap_or_default(),
+            changes,
+        }
+    }
+
+    /// Get temperature points of the class10 regions.
+    ///
+    /// Returns None if the short-term report has no temperatures.
+    pub fn get_temperature_points(&self) -> Option<Vec<Temps>> {
+        serde_json::from_value(self.json[0]["timeSeries"][2]["areas"].clone()).ok()
+    }
+
+    /// Get the peak temperatures of the temperature point.
+    ///
+    /// "Today" is the date of `reportDatetime`.
+    pub fn temperature_forecast(&self, area_code: &str) -> Option<PeakTemp> {
+        let report_datetime = DateTime::parse_from_rfc3339(&self.report_datetime()?).ok()?;
+        self.temperature_forecast_at(area_code, report_datetime)
+    }
+
+    /// Get the peak temperatures of the temperature point for today and tomorrow
+    /// as seen at the reference time.
+    ///
+    /// The dates are in JST regardless of the time zone of `reference`.
+    /// A value at 00:00 is the lowest temperature in the morning of the day,
+    /// and a value at 09:00 is the highest temperature in the daytime.
+    /// Values not in chronological order only repeat another value and are ignored.
+    pub fn temperature_forecast_at<Tz: TimeZone>(
+        &self,
+        area_code: &str,
+        reference: DateTime<Tz>,
+    ) -> Option<PeakTemp> {
+        let report_datetime = self.report_datetime()?;
+        let peaks: PeakTemps =
+            serde_json::from_value(self.json[0]["timeSeries"][2].clone()).ok()?;
+        let area = peaks.areas.iter().find(|a| a.area.code == area_code)?;
+
+        let jst = FixedOffset::east_opt(JST_OFFSET_SECONDS)?;
+        let today_date = reference.with_timezone(&jst).date_naive();
+        let tomorrow_date = today_date.succ_opt()?;
+        let mut today = DailyPeakTemp::new(today_date);
+        let mut tomorrow = DailyPeakTemp::new(tomorrow_date);
+
+        let mut previous: Option<DateTime<FixedOffset>> = None;
+        for (datetime_str, temp) in peaks.time_defines.iter().zip(area.temps.iter()) {
+            let datetime = match DateTime::parse_from_rfc3339(datetime_str) {
+                Ok(dt) => dt.with_timezone(&jst),
+                Err(_) => continue,
+            };
+            if previous.is_some_and(|p| datetime < p) {
+                continue;
+            }
+            previous = Some(datetime);
+
+            let celsius = match temp.parse::<f32>() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            let peak = if datetime.date_naive() == today_date {
+                &mut today
+            } else if datetime.date_naive() == tomorrow_date {
+                &mut tomorrow
+            } else {
+                continue;
+            };
+            let temperature = Some(Temperature {
+                celsius,
+                datetime: datetime_str.clone(),
+            });
+            match datetime.hour() {
+                0 => peak.lowest = temperature,
+                9 => peak.highest = temperature,
+                _ => {}
+            }
+        }
+
+        Some(PeakTemp {
+            report_datetime,
+            area_name: area.area.name.clone(),
+            area_code: area.area.code.clone(),
+            today,
+            tomorrow,
+        })
     }
 }
 
 /// The area name and code of an temperature points.
 ///
 /// - List of AMeDAS observation sites: <https://www.jma.go.jp/bosai/amedas/const/amedastable.json>
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TempsArea {
     /// AMeDAS observation site ID.
     pub code: String,
@@ -372,7 +796,7 @@ pub struct TempsArea {
 /// The temperature points of the class10 regions.
 ///
 /// `{office}.json: [0].timeSeries[2].areas[.]`
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Temps {
     pub area: TempsArea,
     pub temps: Vec<String>,
@@ -381,22 +805,871 @@ pub struct Temps {
 /// The peak temperature and datetime of the class10 regions.
 ///
 /// `{office}.json: [0].timeSeries[2]`
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct PeakTemps {
     #[serde(rename = "timeDefines")]
     pub time_defines: Vec<String>,
     pub areas: Vec<Temps>,
 }
 
-/// The peak temperature and datetime of the area.
+/// A forecast temperature.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Temperature {
+    pub celsius: f32,
+    pub datetime: String,
+}
+
+/// The peak temperatures of a day.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DailyPeakTemp {
+    /// YYYY-MM-DD in JST.
+    pub date: String,
+    pub lowest: Option<Temperature>,
+    pub highest: Option<Temperature>,
+}
+
+impl DailyPeakTemp {
+    fn new(date: NaiveDate) -> Self {
+        DailyPeakTemp {
+            date: date.format("%Y-%m-%d").to_string(),
+            lowest: None,
+            highest: None,
+        }
+    }
+}
+
+/// The peak temperatures of the area for today and tomorrow.
 ///
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PeakTemp {
     pub report_datetime: String,
     pub area_name: String,
     pub area_code: String,
-    pub lowest: String,
-    pub lowest_datetime: String,
-    pub highest: String,
-    pub highest_datetime: String,
+    pub today: DailyPeakTemp,
+    pub tomorrow: DailyPeakTemp,
+}
+
+/// The area name and code of a forecast region.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ForecastAreaCode {
+    pub name: String,
+    pub code: String,
+}
+
+/// A time series of the forecast.
+///
+/// `{office}.json: [.].timeSeries[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TimeSeries<T> {
+    #[serde(rename = "timeDefines", alias = "timeDafines")]
+    pub time_defines: Vec<String>,
+    pub areas: Vec<T>,
+}
+
+/// The weather, wind and wave of the class10 region.
+///
+/// `{office}.json: [0].timeSeries[0].areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherArea {
+    pub area: ForecastAreaCode,
+    pub weather_codes: Vec<String>,
+    pub weathers: Vec<String>,
+    pub winds: Vec<String>,
+    /// Inland regions have no waves.
+    #[serde(default)]
+    pub waves: Vec<String>,
+}
+
+/// The probabilities of precipitation of the class10 region.
+///
+/// `{office}.json: [0].timeSeries[1].areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PopArea {
+    pub area: ForecastAreaCode,
+    pub pops: Vec<String>,
+}
+
+/// The weather of a day.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DailyWeather {
+    pub datetime: String,
+    pub weather_code: String,
+    pub weather: String,
+    pub wind: String,
+    pub wave: Option<String>,
+}
+
+impl DailyWeather {
+    /// Parse the wind text.
+    pub fn parsed_wind(&self) -> WindForecast {
+        parse_wind(&self.wind)
+    }
+
+    /// Parse the weather text.
+    pub fn parsed_weather(&self) -> WeatherText {
+        parse_weather(&self.weather)
+    }
+
+    /// Parse the wave text.
+    pub fn parsed_wave(&self) -> Option<WaveForecast> {
+        self.wave.as_deref().map(parse_wave)
+    }
+}
+
+/// The probability of precipitation in the 6 hours from the datetime.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Pop {
+    pub datetime: String,
+    /// Percent. None if the period has already passed.
+    pub pop: Option<u32>,
+}
+
+/// The short-term forecast of the class10 region.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ShortTermForecast {
+    pub report_datetime: String,
+    pub area_name: String,
+    pub area_code: String,
+    /// Today, tomorrow and the day after tomorrow.
+    pub days: Vec<DailyWeather>,
+    pub pops: Vec<Pop>,
+}
+
+/// The weekly weather of the region.
+///
+/// `{office}.json: [1].timeSeries[0].areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyWeatherArea {
+    pub area: ForecastAreaCode,
+    pub weather_codes: Vec<String>,
+    pub pops: Vec<String>,
+    pub reliabilities: Vec<String>,
+}
+
+/// The weekly temperatures of the temperature point.
+///
+/// `{office}.json: [1].timeSeries[1].areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyTempArea {
+    pub area: TempsArea,
+    pub temps_min: Vec<String>,
+    pub temps_min_upper: Vec<String>,
+    pub temps_min_lower: Vec<String>,
+    pub temps_max: Vec<String>,
+    pub temps_max_upper: Vec<String>,
+    pub temps_max_lower: Vec<String>,
+}
+
+impl WeeklyTempArea {
+    fn range(value: &[String], upper: &[String], lower: &[String], i: usize) -> Option<TempRange> {
+        let parse = |v: &[String]| v.get(i).and_then(|s| s.parse::<f32>().ok());
+        Some(TempRange {
+            value: parse(value)?,
+            upper: parse(upper),
+            lower: parse(lower),
+        })
+    }
+
+    fn min(&self, i: usize) -> Option<TempRange> {
+        Self::range(
+            &self.temps_min,
+            &self.temps_min_upper,
+            &self.temps_min_lower,
+            i,
+        )
+    }
+
+    fn max(&self, i: usize) -> Option<TempRange> {
+        Self::range(
+            &self.temps_max,
+            &self.temps_max_upper,
+            &self.temps_max_lower,
+            i,
+        )
+    }
+}
+
+/// The normal values of the temperature point.
+///
+/// `{office}.json: [1].tempAverage.areas[.]` and `[1].precipAverage.areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AverageArea {
+    pub area: TempsArea,
+    pub min: String,
+    pub max: String,
+}
+
+/// `{office}.json: [1].tempAverage` and `[1].precipAverage`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Averages {
+    pub areas: Vec<AverageArea>,
+}
+
+/// Reliability of the weekly forecast.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Reliability {
+    /// High.
+    A,
+    /// Fairly high.
+    B,
+    /// Fairly low.
+    C,
+}
+
+impl Reliability {
+    /// convert str to Reliability.
+    pub fn to_reliability(reliability: &str) -> Option<Reliability> {
+        match reliability {
+            "A" => Some(Reliability::A),
+            "B" => Some(Reliability::B),
+            "C" => Some(Reliability::C),
+            _ => None,
+        }
+    }
+}
+
+/// A temperature and its confidence interval.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TempRange {
+    pub value: f32,
+    pub upper: Option<f32>,
+    pub lower: Option<f32>,
+}
+
+/// The normal range of the temperature point.
+///
+/// For `tempAverage`, `min` and `max` are the normal lowest and highest temperatures (℃).
+/// For `precipAverage`, they are the bounds of the normal 7-day precipitation (mm).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct NormalRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl NormalRange {
+    /// Parse the normal values; None if either is not a number.
+    pub fn from_average(average: &AverageArea) -> Option<NormalRange> {
+        Some(NormalRange {
+            min: average.min.parse().ok()?,
+            max: average.max.parse().ok()?,
+        })
+    }
+}
+
+/// Temperature difference (℃) from the normal regarded as near normal.
+pub const NEAR_NORMAL_TEMP: f32 = 1.0;
+
+/// Comparison of the forecast with the normal.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum NormalComparison {
+    Above,
+    Near,
+    Below,
+}
+
+impl NormalComparison {
+    /// Compare the temperature with the normal temperature.
+    pub fn compare(temp: f32, normal: f32) -> NormalComparison {
+        if temp - normal > NEAR_NORMAL_TEMP {
+            NormalComparison::Above
+        } else if normal - temp > NEAR_NORMAL_TEMP {
+            NormalComparison::Below
+        } else {
+            NormalComparison::Near
+        }
+    }
+}
+
+/// The weekly forecast of a day.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WeeklyDay {
+    pub datetime: String,
+    pub weather_code: String,
+    /// Percent.
+    pub pop: Option<u32>,
+    pub reliability: Option<Reliability>,
+    pub temp_min: Option<TempRange>,
+    pub temp_max: Option<TempRange>,
+    pub temp_min_normal: Option<NormalComparison>,
+    pub temp_max_normal: Option<NormalComparison>,
+}
+
+/// The weekly forecast of the region.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WeeklyForecast {
+    pub report_datetime: String,
+    pub area_name: String,
+    pub area_code: String,
+    pub temp_area_name: Option<String>,
+    pub temp_area_code: Option<String>,
+    /// Seven days.
+    pub days: Vec<WeeklyDay>,
+    pub normal_temp: Option<NormalRange>,
+    pub normal_precip: Option<NormalRange>,
+}
+
+/// Where the forecast of an area is published.
+#[derive(Serialize, Debug, Clone)]
+pub struct ForecastLocation {
+    /// Office in area.json.
+    pub office: Area,
+    /// Office of the forecast JSON ({office}.json), e.g. 014100 for 十勝地方 (014030).
+    pub forecast_office: String,
+    pub class10: Area,
+}
+
+impl ForecastLocation {
+    /// Find the Office and the Class10 of the area (Class10, Class15 or Class20).
+    pub fn resolve(areas: &Areas, area: &Area) -> Option<ForecastLocation> {
+        if matches!(area.class, JmaAreaClass::Center | JmaAreaClass::Office) {
+            return None;
+        }
+        let class10 = areas.ancestor(area, &JmaAreaClass::Class10)?;
+        let office = areas.ancestor(&class10, &JmaAreaClass::Office)?;
+        Some(ForecastLocation {
+            forecast_office: office_for_url(&office.code).to_string(),
+            office,
+            class10,
+        })
+    }
+}
+
+/// The forecast of an area.
+#[derive(Serialize, Debug, Clone)]
+pub struct AreaForecast {
+    pub location: ForecastLocation,
+    pub short_term: ShortTermForecast,
+    pub weekly: Option<WeeklyForecast>,
+}
+
+/// The forecast value that changed.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum ForecastField {
+    WeatherCode,
+    Pop,
+    Temp,
+    WeeklyWeatherCode,
+    WeeklyPop,
+    WeeklyTempMin,
+    WeeklyTempMax,
+}
+
+impl fmt::Display for ForecastField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let field = match self {
+            ForecastField::WeatherCode => "weather code",
+            ForecastField::Pop => "chance of rain",
+            ForecastField::Temp => "temperature",
+            ForecastField::WeeklyWeatherCode => "weekly weather code",
+            ForecastField::WeeklyPop => "weekly chance of rain",
+            ForecastField::WeeklyTempMin => "weekly lowest temperature",
+            ForecastField::WeeklyTempMax => "weekly highest temperature",
+        };
+        write!(f, "{}", field)
+    }
+}
+
+/// A change of a forecast value between two releases.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Change {
+    pub area_name: String,
+    pub area_code: String,
+    pub field: ForecastField,
+    pub datetime: String,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+    /// `current - previous` for numeric values.
+    pub delta: Option<f32>,
+}
+
+impl Change {
+    fn push(
+        changes: &mut Vec<Change>,
+        area: &ForecastAreaCode,
+        field: ForecastField,
+        datetime: &str,
+        previous: Option<String>,
+        current: Option<String>,
+    ) {
+        if previous == current {
+            return;
+        }
+        changes.push(Change {
+            area_name: area.name.clone(),
+            area_code: area.code.clone(),
+            field,
+            datetime: datetime.to_string(),
+            previous,
+            current,
+            delta: None,
+        });
+    }
+
+    fn push_number(
+        changes: &mut Vec<Change>,
+        area: &ForecastAreaCode,
+        field: ForecastField,
+        datetime: &str,
+        previous: Option<f32>,
+        current: Option<f32>,
+    ) {
+        if previous.is_none() || current.is_none() || previous == current {
+            return;
+        }
+        Change::push(
+            changes,
+            area,
+            field,
+            datetime,
+            previous.map(|v| v.to_string()),
+            current.map(|v| v.to_string()),
+        );
+        if let Some(change) = changes.last_mut() {
+            change.delta = previous.zip(current).map(|(p, c)| c - p);
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    /// e.g. "津軽 2025-03-29T12:00:00+09:00 chance of rain raised from 30 to 70".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let previous = self.previous.as_deref().unwrap_or("-");
+        let current = self.current.as_deref().unwrap_or("-");
+        let verb = match self.delta {
+            Some(d) if d > 0.0 => "raised",
+            Some(d) if d < 0.0 => "lowered",
+            _ => "changed",
+        };
+        write!(
+            f,
+            "{} {} {} {} from {} to {}",
+            self.area_name, self.datetime, self.field, verb, previous, current
+        )
+    }
+}
+
+/// Changes between two releases of a forecast.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ForecastDiff {
+    pub previous_report_datetime: String,
+    pub report_datetime: String,
+    pub changes: Vec<Change>,
+}
+
+impl ForecastDiff {
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) const AOMORI_JSON: &str = r#"[
+  {
+    "publishingOffice": "青森地方気象台",
+    "reportDatetime": "2025-03-28T17:00:00+09:00",
+    "timeSeries": [
+      {
+        "timeDefines": [
+          "2025-03-28T17:00:00+09:00",
+          "2025-03-29T00:00:00+09:00",
+          "2025-03-30T00:00:00+09:00"
+        ],
+        "areas": [
+          {
+            "area": { "name": "津軽", "code": "020010" },
+            "weatherCodes": ["200", "200", "270"],
+            "weathers": [
+              "くもり　所により　雨",
+              "くもり　所により　昼過ぎ　から　よるのはじめ頃　雪",
+              "くもり　時々　雪か雨"
+            ],
+            "winds": [
+              "西の風　後　やや強く　海上　では　後　西の風　強く",
+              "西の風　はじめ　やや強く　海上　では　西の風　強く",
+              "西の風　やや強く"
+            ],
+            "waves": [
+              "１．５メートル　後　２メートル",
+              "２．５メートル　後　１．５メートル",
+              "１．５メートル　後　３メートル"
+            ]
+          }
+        ]
+      },
+      {
+        "timeDefines": [
+          "2025-03-28T18:00:00+09:00",
+          "2025-03-29T00:00:00+09:00",
+          "2025-03-29T06:00:00+09:00",
+          "2025-03-29T12:00:00+09:00",
+          "2025-03-29T18:00:00+09:00"
+        ],
+        "areas": [
+          {
+            "area": { "name": "津軽", "code": "020010" },
+            "pops": ["30", "10", "10", "30", "20"]
+          }
+        ]
+      },
+      {
+        "timeDefines": [
+          "2025-03-29T00:00:00+09:00",
+          "2025-03-29T09:00:00+09:00"
+        ],
+        "areas": [
+          {
+            "area": { "name": "青森", "code": "31312" },
+            "temps": ["3", "9"]
+          }
+        ]
+      }
+    ]
+  },
+  {
+    "publishingOffice": "青森地方気象台",
+    "reportDatetime": "2025-03-28T17:00:00+09:00",
+    "timeSeries": [
+      {
+        "timeDefines": [
+          "2025-03-29T00:00:00+09:00",
+          "2025-03-30T00:00:00+09:00",
+          "2025-03-31T00:00:00+09:00",
+          "2025-04-01T00:00:00+09:00",
+          "2025-04-02T00:00:00+09:00",
+          "2025-04-03T00:00:00+09:00",
+          "2025-04-04T00:00:00+09:00"
+        ],
+        "areas": [
+          {
+            "area": { "name": "津軽・下北", "code": "020100" },
+            "weatherCodes": ["200", "270", "201", "201", "202", "202", "201"],
+            "pops": ["", "70", "30", "20", "50", "50", "30"],
+            "reliabilities": ["", "", "B", "A", "C", "C", "B"]
+          }
+        ]
+      },
+      {
+        "timeDefines": [
+          "2025-03-29T00:00:00+09:00",
+          "2025-03-30T00:00:00+09:00",
+          "2025-03-31T00:00:00+09:00",
+          "2025-04-01T00:00:00+09:00",
+          "2025-04-02T00:00:00+09:00",
+          "2025-04-03T00:00:00+09:00",
+          "2025-04-04T00:00:00+09:00"
+        ],
+        "areas": [
+          {
+            "area": { "name": "青森", "code": "31312" },
+            "tempsMin": ["", "-1", "0", "-1", "2", "3", "3"],
+            "tempsMinUpper": ["", "1", "1", "1", "4", "5", "5"],
+            "tempsMinLower": ["", "-3", "-2", "-4", "-2", "1", "0"],
+            "tempsMax": ["", "5", "6", "10", "11", "9", "11"],
+            "tempsMaxUpper": ["", "9", "8", "13", "14", "13", "14"],
+            "tempsMaxLower": ["", "4", "4", "8", "8", "6", "8"]
+          }
+        ]
+      }
+    ],
+    "tempAverage": {
+      "areas": [
+        {
+          "area": { "name": "青森", "code": "31312" },
+          "min": "1.5",
+          "max": "10.3"
+        }
+      ]
+    },
+    "precipAverage": {
+      "areas": [
+        {
+          "area": { "name": "青森", "code": "31312" },
+          "min": "7.6",
+          "max": "19.5"
+        }
+      ]
+    }
+  }
+]"#;
+
+    pub(crate) fn aomori() -> JmaForecast {
+        JmaForecast::from_json_str(AOMORI_JSON).unwrap()
+    }
+
+    #[test]
+    fn short_term() {
+        let forecast = aomori();
+        let short = forecast.short_term("020010").unwrap();
+        assert_eq!(short.report_datetime, "2025-03-28T17:00:00+09:00");
+        assert_eq!(short.area_name, "津軽");
+        assert_eq!(short.days.len(), 3);
+        assert_eq!(short.days[2].weather_code, "270");
+        assert_eq!(
+            short.days[1].weather,
+            "くもり　所により　昼過ぎ　から　よるのはじめ頃　雪"
+        );
+        assert_eq!(
+            short.days[0].wind,
+            "西の風　後　やや強く　海上　では　後　西の風　強く"
+        );
+        assert_eq!(
+            short.days[0].wave,
+            Some("１．５メートル　後　２メートル".to_string())
+        );
+        assert_eq!(short.days[0].parsed_wave().unwrap().max_m(), Some(2.0));
+        assert_eq!(short.pops.len(), 5);
+        assert_eq!(short.pops[3].datetime, "2025-03-29T12:00:00+09:00");
+        assert_eq!(short.pops[3].pop, Some(30));
+
+        assert!(forecast.short_term("020020").is_none());
+    }
+
+    #[test]
+    fn invalid_shape() {
+        assert!(JmaForecast::from_json_str("[]").is_err());
+        assert!(JmaForecast::from_json_str("{}").is_err());
+        assert!(JmaForecast::from_json_str(r#"[{"reportDatetime": ""}]"#).is_err());
+
+        let forecast = JmaForecast::from_json_str(r#"[{"timeSeries": []}]"#).unwrap();
+        assert!(forecast.get_temperature_points().is_none());
+        assert!(forecast.temperature_forecast("31312").is_none());
+        assert_eq!(
+            aomori().get_temperature_points().unwrap()[0].area.code,
+            "31312"
+        );
+    }
+
+    #[test]
+    fn area_forecast() {
+        let areas = Areas::from_value(serde_json::json!({
+            "centers": {
+                "010100": {"name": "北海道地方", "enName": "Hokkaido", "officeName": "札幌管区気象台", "children": ["014030"]},
+                "010200": {"name": "東北地方", "enName": "Tohoku", "officeName": "仙台管区気象台", "children": ["020000"]}
+            },
+            "offices": {
+                "014030": {"name": "十勝地方", "enName": "Tokachi", "officeName": "帯広測候所", "parent": "010100", "children": ["014030"]},
+                "020000": {"name": "青森県", "enName": "Aomori", "officeName": "青森地方気象台", "parent": "010200", "children": ["020010"]}
+            },
+            "class10s": {
+                "014030": {"name": "十勝地方", "enName": "Tokachi", "parent": "014030", "children": ["014031"]},
+                "020010": {"name": "津軽", "enName": "Tsugaru", "parent": "020000", "children": ["020011"]}
+            },
+            "class15s": {
+                "014031": {"name": "十勝北部", "enName": "Northern Tokachi", "parent": "014030", "children": ["0120700"]},
+                "020011": {"name": "東青津軽", "enName": "Tosei Tsugaru", "parent": "020010", "children": ["0220100"]}
+            },
+            "class20s": {
+                "0120700": {"name": "帯広市", "enName": "Obihiro City", "kana": "おびひろし", "parent": "014031"},
+                "0220100": {"name": "青森市", "enName": "Aomori City", "kana": "あおもりし", "parent": "020011"}
+            }
+        }))
+        .unwrap();
+
+        let obihiro = areas.values(&JmaAreaClass::Class20, "0120700").unwrap();
+        let location = ForecastLocation::resolve(&areas, &obihiro).unwrap();
+        assert_eq!(location.office.code, "014030");
+        assert_eq!(location.forecast_office, "014100");
+        assert_eq!(location.class10.code, "014030");
+        let office = areas.values(&JmaAreaClass::Office, "020000").unwrap();
+        assert!(ForecastLocation::resolve(&areas, &office).is_none());
+
+        let aomori_city = areas.values(&JmaAreaClass::Class20, "0220100").unwrap();
+        let location = ForecastLocation::resolve(&areas, &aomori_city).unwrap();
+        assert_eq!(location.forecast_office, "020000");
+        let forecast_area = ForecastArea::from_value(serde_json::json!({
+            "020000": [{"class10": "020010", "amedas": ["31312"], "class20": "0220100"}]
+        }))
+        .unwrap();
+        let area_forecast = aomori()
+            .area_forecast(&location, Some(&forecast_area))
+            .unwrap();
+        assert_eq!(area_forecast.short_term.area_name, "津軽");
+        assert_eq!(area_forecast.weekly.unwrap().area_name, "津軽・下北");
+        // The only weekly region without forecast_area.json
+        let area_forecast = aomori().area_forecast(&location, None).unwrap();
+        assert_eq!(area_forecast.weekly.unwrap().area_code, "020100");
+
+        // A weekly region with several temperature points
+        let mut json: Value = serde_json::from_str(AOMORI_JSON).unwrap();
+        let mut mutsu = json[1]["timeSeries"][1]["areas"][0].clone();
+        mutsu["area"] = serde_json::json!({"name": "むつ", "code": "31602"});
+        mutsu["tempsMax"][3] = Value::from("1");
+        json[1]["timeSeries"][1]["areas"]
+            .as_array_mut()
+            .unwrap()
+            .insert(0, mutsu);
+        let forecast = JmaForecast::from_value(json).unwrap();
+        let weekly = forecast
+            .area_forecast(&location, Some(&forecast_area))
+            .unwrap()
+            .weekly
+            .unwrap();
+        assert_eq!(weekly.temp_area_code, Some("31312".to_string()));
+        let weekly = forecast
+            .area_forecast(&location, None)
+            .unwrap()
+            .weekly
+            .unwrap();
+        assert_eq!(weekly.temp_area_code, None);
+    }
+
+    #[test]
+    fn weekly() {
+        let forecast = aomori();
+        let weekly = forecast.weekly("020100", Some("31312")).unwrap();
+        assert_eq!(weekly.area_name, "津軽・下北");
+        assert_eq!(weekly.temp_area_code, Some("31312".to_string()));
+        assert_eq!(weekly.days.len(), 7);
+        assert_eq!(weekly.days[0].pop, None);
+        assert_eq!(weekly.days[0].reliability, None);
+        assert_eq!(weekly.days[0].temp_min, None);
+        assert_eq!(weekly.days[1].pop, Some(70));
+        assert_eq!(weekly.days[3].reliability, Some(Reliability::A));
+        assert_eq!(
+            weekly.days[3].temp_min,
+            Some(TempRange {
+                value: -1.0,
+                upper: Some(1.0),
+                lower: Some(-4.0)
+            })
+        );
+        assert_eq!(
+            weekly.days[3].temp_min_normal,
+            Some(NormalComparison::Below)
+        );
+        assert_eq!(weekly.days[3].temp_max_normal, Some(NormalComparison::Near));
+        assert_eq!(
+            weekly.days[2].temp_max_normal,
+            Some(NormalComparison::Below)
+        );
+        assert_eq!(
+            weekly.normal_precip,
+            Some(NormalRange {
+                min: 7.6,
+                max: 19.5
+            })
+        );
+        let blank = AverageArea {
+            area: TempsArea {
+                code: "31312".to_string(),
+                name: "青森".to_string(),
+            },
+            min: "".to_string(),
+            max: "19.5".to_string(),
+        };
+        assert_eq!(NormalRange::from_average(&blank), None);
+
+        // Without the temperature point
+        let weekly = forecast.weekly("020100", None).unwrap();
+        assert_eq!(weekly.temp_area_code, None);
+        assert_eq!(weekly.days[3].temp_min, None);
+        assert_eq!(weekly.normal_temp, None);
+        assert_eq!(weekly.days[1].pop, Some(70));
+        let weekly = forecast.weekly("020100", Some("99999")).unwrap();
+        assert_eq!(weekly.temp_area_code, None);
+        assert!(forecast.weekly("31312", None).is_none());
+        assert!(forecast.weekly("020010", None).is_none());
+    }
+
+    #[test]
+    fn temperature_forecast_evening() {
+        let forecast = aomori();
+        let peak = forecast.temperature_forecast("31312").unwrap();
+        assert_eq!(peak.area_name, "青森");
+        assert_eq!(peak.today.date, "2025-03-28");
+        assert_eq!(peak.today.lowest, None);
+        assert_eq!(peak.today.highest, None);
+        assert_eq!(peak.tomorrow.date, "2025-03-29");
+        assert_eq!(
+            peak.tomorrow.lowest,
+            Some(Temperature {
+                celsius: 3.0,
+                datetime: "2025-03-29T00:00:00+09:00".to_string()
+            })
+        );
+        assert_eq!(
+            peak.tomorrow.highest,
+            Some(Temperature {
+                celsius: 9.0,
+                datetime: "2025-03-29T09:00:00+09:00".to_string()
+            })
+        );
+
+        // 2025-03-29T05:00:00+09:00 on a UTC server.
+        let reference = DateTime::parse_from_rfc3339("2025-03-28T20:00:00Z").unwrap();
+        let peak = forecast
+            .temperature_forecast_at("31312", reference)
+            .unwrap();
+        assert_eq!(peak.today.date, "2025-03-29");
+        assert_eq!(peak.today.lowest.unwrap().celsius, 3.0);
+        assert_eq!(peak.tomorrow.highest, None);
+
+        assert!(forecast.temperature_forecast("00000").is_none());
+    }
+
+    #[test]
+    fn temperature_forecast_daytime() {
+        let json = serde_json::json!([{
+            "reportDatetime": "2025-11-18T11:00:00+09:00",
+            "timeSeries": [{}, {}, {
+                "timeDefines": [
+                    "2025-11-18T09:00:00+09:00",
+                    "2025-11-18T00:00:00+09:00",
+                    "2025-11-19T00:00:00+09:00",
+                    "2025-11-19T09:00:00+09:00"
+                ],
+                "areas": [{
+                    "area": { "name": "札幌", "code": "14163" },
+                    "temps": ["2", "2", "-1", "3"]
+                }]
+            }]
+        }]);
+        let forecast = JmaForecast { json };
+        let peak = forecast.temperature_forecast("14163").unwrap();
+        assert_eq!(peak.today.lowest, None);
+        assert_eq!(peak.today.highest.unwrap().celsius, 2.0);
+        assert_eq!(peak.tomorrow.lowest.unwrap().celsius, -1.0);
+        assert_eq!(peak.tomorrow.highest.unwrap().celsius, 3.0);
+    }
+
+    #[test]
+    fn diff() {
+        let previous = aomori();
+        let mut json: Value = serde_json::from_str(AOMORI_JSON).unwrap();
+        json[0]["reportDatetime"] = Value::from("2025-03-28T20:00:00+09:00");
+        json[0]["timeSeries"][1]["areas"][0]["pops"][3] = Value::from("70");
+        json[0]["timeSeries"][0]["areas"][0]["weatherCodes"][1] = Value::from("203");
+        json[0]["timeSeries"][2]["areas"][0]["temps"][1] = Value::from("7");
+        json[1]["timeSeries"][1]["areas"][0]["tempsMax"][6] = Value::from("13");
+        let current = JmaForecast { json };
+
+        assert!(current.diff(&current).is_empty());
+
+        let diff = current.diff(&previous);
+        assert_eq!(diff.previous_report_datetime, "2025-03-28T17:00:00+09:00");
+        assert_eq!(diff.report_datetime, "2025-03-28T20:00:00+09:00");
+        assert_eq!(diff.changes.len(), 4);
+        assert_eq!(diff.changes[0].field, ForecastField::WeatherCode);
+        assert_eq!(diff.changes[0].current, Some("203".to_string()));
+        assert_eq!(diff.changes[0].delta, None);
+        assert_eq!(diff.changes[1].field, ForecastField::Pop);
+        assert_eq!(diff.changes[1].delta, Some(40.0));
+        assert_eq!(
+            diff.changes[1].to_string(),
+            "津軽 2025-03-29T12:00:00+09:00 chance of rain raised from 30 to 70"
+        );
+        assert_eq!(diff.changes[2].field, ForecastField::Temp);
+        assert_eq!(diff.changes[2].area_code, "31312");
+        assert_eq!(diff.changes[2].delta, Some(-2.0));
+        assert_eq!(diff.changes[3].field, ForecastField::WeeklyTempMax);
+        assert_eq!(diff.changes[3].area_code, "31312");
+        assert_eq!(diff.changes[3].datetime, "2025-04-04T00:00:00+09:00");
+    }
 }
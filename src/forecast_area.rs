@@ -21,9 +21,10 @@
 //! ```
 //!
 //! ## Example
-//! ```rust
+//! ```rust,no_run
 //! use jma::forecast_area::ForecastArea;
 //!
+//! # #[cfg(feature = "http")]
 //! #[tokio::main]
 //! async fn main() {
 //!   let forecast_area = ForecastArea::new().await.unwrap();
@@ -34,15 +35,17 @@
 //!   assert_eq!(forecast_area.offices["200000"][1].amedas[2], "48331");
 //!   assert_eq!(forecast_area.offices["200000"][1].class20, "2020201");
 //! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
 //! ```
 
-use reqwest::Error;
-use serde::Deserialize;
-use serde_json::Value;
+#[cfg(feature = "http")]
+use crate::client::{ClientError, Resource, default_client};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// AMEDAS Observation Site.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct AmedasObservationSite {
     pub class10: String,
     pub amedas: Vec<String>,
@@ -50,7 +53,9 @@ pub struct AmedasObservationSite {
 }
 
 /// AMEDAS Observation Sites in Forecast Areas.
-#[derive(Deserialize, Debug)]
+///
+/// The lookups scan the offices; build a [`CrossIndex`](crate::xref::CrossIndex) for many lookups.
+#[derive(Deserialize, Serialize, Debug)]
 pub struct ForecastArea {
     #[serde(flatten)]
     pub offices: HashMap<String, Vec<AmedasObservationSite>>,
@@ -58,38 +63,48 @@ pub struct ForecastArea {
 
 impl ForecastArea {
     /// Fetch forecast_area.json.
-    pub async fn new() -> Result<Self, Error> {
+    #[cfg(feature = "http")]
+    pub async fn new() -> Result<Self, ClientError> {
         let url = "https://www.jma.go.jp/bosai/forecast/const/forecast_area.json";
-        let forecast_area_json = reqwest::get(url).await?.json::<Value>().await?;
-        let area: ForecastArea = serde_json::from_value(forecast_area_json.clone()).unwrap();
-        Ok(area)
+        default_client().get_json(url, Resource::Constant).await
+    }
+
+    /// Parse forecast_area.json.
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse forecast_area.json from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed forecast_area.json.
+    pub fn from_value(json: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(json)
     }
 
     /// Get AMeDAS observation site.
     pub fn get_amedas_by_class10(&self, code: &str) -> Option<&Vec<String>> {
-        for (_, offices) in &self.offices {
-            for office in offices {
-                if office.class10 == code {
-                    return Some(&office.amedas);
-                }
-            }
-        }
-        None
+        self.offices
+            .values()
+            .flatten()
+            .find(|site| site.class10 == code)
+            .map(|site| &site.amedas)
     }
 
-    /// Get the office of the AMeDAS station.
+    /// Get the office of the AMeDAS station, the lowest code if several offices list it.
     pub fn get_office_by_amedas(&self, amedas_code: &str) -> Option<String> {
-        for (office_code, offices) in &self.offices {
-            let site = offices
-                .iter()
-                .find(|o| {
-                    o.amedas.iter().any(|c| c == amedas_code)
-                });
-            if site.is_some() {
-                return Some(office_code.clone());
-            }
-        };
-        None
+        self.offices
+            .iter()
+            .filter(|(_, sites)| {
+                sites
+                    .iter()
+                    .any(|site| site.amedas.iter().any(|c| c == amedas_code))
+            })
+            .map(|(office, _)| office)
+            .min()
+            .cloned()
     }
 }
 
@@ -97,6 +112,24 @@ impl ForecastArea {
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_value() {
+        let json = serde_json::json!({
+            "200000": [{"class10": "200010", "amedas": ["48156"], "class20": "2020100"}]
+        });
+        let forecast_area = ForecastArea::from_value(json).unwrap();
+        assert_eq!(
+            forecast_area.get_office_by_amedas("48156").unwrap(),
+            "200000"
+        );
+        assert_eq!(forecast_area.get_office_by_amedas("48361"), None);
+        assert_eq!(
+            forecast_area.get_amedas_by_class10("200010").unwrap(),
+            &vec!["48156"]
+        );
+    }
+
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn get_forecast_area() {
         let forecast_area = ForecastArea::new().await.unwrap();
@@ -108,6 +141,7 @@ mod tests {
         assert_eq!(forecast_area.offices["200000"][1].class20, "2020201");
     }
 
+    #[cfg(feature = "http")]
     #[tokio::test]
     async fn get_amedas_by_class10() {
         let forecast_area = ForecastArea::new().await.unwrap();
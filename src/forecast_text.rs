@@ -0,0 +1,899 @@
+//! # Parse Forecast Text
+//!
+//! `{office}.json: [0].timeSeries[0].areas[.]` carries the winds and the waves as Japanese text
+//! whose words are separated by full-width spaces.
+//!
+//! ```json
+//! "winds": [
+//!   "西の風　後　やや強く　海上　では　後　西の風　強く"
+//! ],
+//! "waves": [
+//!   "１．５メートル　後　２メートル"
+//! ]
+//! ```
+//!
+//! ## Example
+//! ```rust
+//! use jma::forecast_text::{parse_wave, parse_wind, WindArea, WindDirection, WindStrength};
+//!
+//! let wind = parse_wind("西の風　後　やや強く　海上　では　後　西の風　強く");
+//! assert_eq!(wind.sections[0].area, WindArea::Land);
+//! assert_eq!(wind.sections[0].phrases[1].direction, Some(WindDirection::W));
+//! assert_eq!(wind.sections[0].phrases[1].strength, Some(WindStrength::SomewhatStrong));
+//! assert_eq!(wind.sections[1].area, WindArea::Sea);
+//! assert_eq!(wind.sections[1].phrases[0].strength, Some(WindStrength::Strong));
+//!
+//! let wave = parse_wave("１．５メートル　後　２メートル");
+//! assert_eq!(wave.phrases[0].height.min_m, 1.5);
+//! assert_eq!(wave.max_m(), Some(2.0));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::mem;
+
+/// Convert full-width ASCII characters and full-width spaces to half-width.
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Split the text into words.
+pub fn tokens(text: &str) -> Vec<String> {
+    normalize(text)
+        .split_whitespace()
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Time of day used in forecast text (時間細分).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum TimeOfDay {
+    /// 未明 (0-3h)
+    LateNight,
+    /// 明け方 (3-6h)
+    Dawn,
+    /// 朝 (6-9h)
+    Morning,
+    /// 昼前 (9-12h)
+    LateMorning,
+    /// 昼頃 (11-13h)
+    Noon,
+    /// 昼過ぎ (12-15h)
+    Afternoon,
+    /// 夕方 (15-18h)
+    Evening,
+    /// 日中 (9-18h)
+    Daytime,
+    /// 夜のはじめ頃 (18-21h)
+    EarlyNight,
+    /// 夜遅く (21-24h)
+    LateNightBeforeMidnight,
+    /// 夜 (18-24h)
+    Night,
+}
+
+impl TimeOfDay {
+    /// convert str to TimeOfDay.
+    pub fn to_time_of_day(word: &str) -> Option<TimeOfDay> {
+        let word = word.replace("よる", "夜");
+        match word.as_str() {
+            "未明" => Some(TimeOfDay::LateNight),
+            "明け方" => Some(TimeOfDay::Dawn),
+            "朝" => Some(TimeOfDay::Morning),
+            "昼前" => Some(TimeOfDay::LateMorning),
+            "昼頃" => Some(TimeOfDay::Noon),
+            "昼過ぎ" => Some(TimeOfDay::Afternoon),
+            "夕方" => Some(TimeOfDay::Evening),
+            "日中" => Some(TimeOfDay::Daytime),
+            "夜のはじめ頃" => Some(TimeOfDay::EarlyNight),
+            "夜遅く" => Some(TimeOfDay::LateNightBeforeMidnight),
+            "夜" => Some(TimeOfDay::Night),
+            _ => None,
+        }
+    }
+
+    /// Returns the English name.
+    pub fn english(&self) -> &'static str {
+        match self {
+            TimeOfDay::LateNight => "after midnight",
+            TimeOfDay::Dawn => "dawn",
+            TimeOfDay::Morning => "morning",
+            TimeOfDay::LateMorning => "late morning",
+            TimeOfDay::Noon => "around noon",
+            TimeOfDay::Afternoon => "afternoon",
+            TimeOfDay::Evening => "evening",
+            TimeOfDay::Daytime => "daytime",
+            TimeOfDay::EarlyNight => "early night",
+            TimeOfDay::LateNightBeforeMidnight => "late night",
+            TimeOfDay::Night => "night",
+        }
+    }
+
+    /// Returns the start and end hours.
+    pub fn hours(&self) -> (u32, u32) {
+        match self {
+            TimeOfDay::LateNight => (0, 3),
+            TimeOfDay::Dawn => (3, 6),
+            TimeOfDay::Morning => (6, 9),
+            TimeOfDay::LateMorning => (9, 12),
+            TimeOfDay::Noon => (11, 13),
+            TimeOfDay::Afternoon => (12, 15),
+            TimeOfDay::Evening => (15, 18),
+            TimeOfDay::Daytime => (9, 18),
+            TimeOfDay::EarlyNight => (18, 21),
+            TimeOfDay::LateNightBeforeMidnight => (21, 24),
+            TimeOfDay::Night => (18, 24),
+        }
+    }
+}
+
+/// Temporal and spatial qualifiers used in forecast text.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Qualifier {
+    /// 後 (later)
+    Later,
+    /// はじめ (at first)
+    AtFirst,
+    /// 時々 (sometimes)
+    Sometimes,
+    /// 一時 (temporarily)
+    Temporarily,
+    /// 所により (locally)
+    Locally,
+    /// 日中, 夜, ...
+    Time(TimeOfDay),
+}
+
+impl Qualifier {
+    /// convert str to Qualifier.
+    pub fn to_qualifier(word: &str) -> Option<Qualifier> {
+        match word {
+            "後" | "のち" => Some(Qualifier::Later),
+            "はじめ" => Some(Qualifier::AtFirst),
+            "時々" => Some(Qualifier::Sometimes),
+            "一時" => Some(Qualifier::Temporarily),
+            "所により" | "ところにより" => Some(Qualifier::Locally),
+            _ => TimeOfDay::to_time_of_day(word).map(Qualifier::Time),
+        }
+    }
+
+    /// Returns the English word.
+    pub fn english(&self) -> &'static str {
+        match self {
+            Qualifier::Later => "then",
+            Qualifier::AtFirst => "at first",
+            Qualifier::Sometimes => "occasionally",
+            Qualifier::Temporarily => "temporarily",
+            Qualifier::Locally => "locally",
+            Qualifier::Time(time) => time.english(),
+        }
+    }
+}
+
+/// Wind direction in 8 points.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum WindDirection {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl WindDirection {
+    /// convert str such as "北東" to WindDirection.
+    pub fn to_wind_direction(direction: &str) -> Option<WindDirection> {
+        match direction {
+            "北" => Some(WindDirection::N),
+            "北東" => Some(WindDirection::NE),
+            "東" => Some(WindDirection::E),
+            "南東" => Some(WindDirection::SE),
+            "南" => Some(WindDirection::S),
+            "南西" => Some(WindDirection::SW),
+            "西" => Some(WindDirection::W),
+            "北西" => Some(WindDirection::NW),
+            _ => None,
+        }
+    }
+}
+
+/// Wind strength category. No category means the wind is not strong.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum WindStrength {
+    /// やや強く (10-15 m/s)
+    SomewhatStrong,
+    /// 強く (15-20 m/s)
+    Strong,
+    /// 非常に強く (20 m/s or more)
+    VeryStrong,
+}
+
+impl WindStrength {
+    /// convert str to WindStrength.
+    pub fn to_wind_strength(strength: &str) -> Option<WindStrength> {
+        match strength {
+            "やや強く" => Some(WindStrength::SomewhatStrong),
+            "強く" => Some(WindStrength::Strong),
+            "非常に強く" => Some(WindStrength::VeryStrong),
+            _ => None,
+        }
+    }
+}
+
+/// Where the wind forecast applies.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum WindArea {
+    /// The forecast region.
+    Land,
+    /// 海上
+    Sea,
+    /// A named area such as "三陸沖".
+    Named(String),
+}
+
+/// A wind in a period.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WindPhrase {
+    pub qualifiers: Vec<Qualifier>,
+    pub direction: Option<WindDirection>,
+    pub strength: Option<WindStrength>,
+}
+
+/// The winds in an area.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WindSection {
+    pub area: WindArea,
+    pub phrases: Vec<WindPhrase>,
+}
+
+/// Parsed wind forecast.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WindForecast {
+    pub sections: Vec<WindSection>,
+    /// Words that could not be parsed.
+    pub others: Vec<String>,
+}
+
+impl WindForecast {
+    /// Returns the strongest category in the forecast.
+    pub fn max_strength(&self) -> Option<WindStrength> {
+        self.sections
+            .iter()
+            .flat_map(|s| s.phrases.iter())
+            .filter_map(|p| p.strength)
+            .fold(None, |max, s| match max {
+                Some(m) if m >= s => Some(m),
+                _ => Some(s),
+            })
+    }
+}
+
+/// Parse wind text such as "西の風　後　やや強く　海上　では　後　西の風　強く".
+pub fn parse_wind(text: &str) -> WindForecast {
+    let words = tokens(text);
+    let mut sections = vec![WindSection {
+        area: WindArea::Land,
+        phrases: Vec::new(),
+    }];
+    let mut others = Vec::new();
+    let mut qualifiers = Vec::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = &words[i];
+        if words.get(i + 1).is_some_and(|w| w == "では") {
+            let area = match word.as_str() {
+                "海上" => WindArea::Sea,
+                _ => WindArea::Named(word.clone()),
+            };
+            sections.push(WindSection {
+                area,
+                phrases: Vec::new(),
+            });
+            i += 2;
+            continue;
+        }
+
+        let section = sections.last_mut().unwrap();
+        let direction = word
+            .strip_suffix("の風")
+            .map(|d| d.trim_end_matches("より"))
+            .and_then(WindDirection::to_wind_direction);
+        if let Some(direction) = direction {
+            section.phrases.push(WindPhrase {
+                qualifiers: mem::take(&mut qualifiers),
+                direction: Some(direction),
+                strength: None,
+            });
+        } else if let Some(strength) = WindStrength::to_wind_strength(word) {
+            match section.phrases.last_mut() {
+                Some(last) if qualifiers.is_empty() && last.strength.is_none() => {
+                    last.strength = Some(strength);
+                }
+                last => {
+                    // e.g. "後　やや強く": the same direction becomes stronger.
+                    let direction = last.and_then(|l| l.direction);
+                    section.phrases.push(WindPhrase {
+                        qualifiers: mem::take(&mut qualifiers),
+                        direction,
+                        strength: Some(strength),
+                    });
+                }
+            }
+        } else if let Some(qualifier) = Qualifier::to_qualifier(word) {
+            qualifiers.push(qualifier);
+        } else {
+            others.push(word.clone());
+        }
+        i += 1;
+    }
+
+    WindForecast { sections, others }
+}
+
+/// Wave height. `min_m` and `max_m` are the same unless it is a range.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct WaveHeight {
+    pub min_m: f32,
+    pub max_m: f32,
+}
+
+impl WaveHeight {
+    /// convert str such as "1.5メートル", "1から1.5メートル" or "1～1.5メートル" to WaveHeight.
+    pub fn to_wave_height(height: &str) -> Option<WaveHeight> {
+        let height = normalize(height);
+        let height = height.strip_suffix("メートル")?;
+        // normalize() has turned the full-width tilde (U+FF5E) into '~'; U+301C stays as is.
+        let (min, max) = match height
+            .split_once("から")
+            .or_else(|| height.split_once(['~', '〜']))
+        {
+            Some((min, max)) => (min.trim_end_matches("メートル"), max),
+            None => (height, height),
+        };
+        Some(WaveHeight {
+            min_m: min.parse().ok()?,
+            max_m: max.parse().ok()?,
+        })
+    }
+}
+
+/// A wave height in a period.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WavePhrase {
+    pub qualifiers: Vec<Qualifier>,
+    /// A named area such as "三陸沖", or None for the whole sea.
+    pub area: Option<String>,
+    pub height: WaveHeight,
+}
+
+/// Parsed wave forecast.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WaveForecast {
+    pub phrases: Vec<WavePhrase>,
+    /// うねり　を伴う
+    pub swell: bool,
+    /// Words that could not be parsed.
+    pub others: Vec<String>,
+}
+
+impl WaveForecast {
+    /// Returns the highest wave in the forecast.
+    pub fn max_m(&self) -> Option<f32> {
+        self.phrases
+            .iter()
+            .map(|p| p.height.max_m)
+            .fold(None, |max, h| Some(max.map_or(h, |m: f32| m.max(h))))
+    }
+}
+
+/// Parse wave text such as "１．５メートル　後　２メートル".
+pub fn parse_wave(text: &str) -> WaveForecast {
+    let words = tokens(text);
+    let mut phrases = Vec::new();
+    let mut others = Vec::new();
+    let mut qualifiers = Vec::new();
+    let mut swell = false;
+    let mut area = None;
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = &words[i];
+        if words.get(i + 1).is_some_and(|w| w == "では") {
+            area = Some(word.clone());
+            i += 2;
+            continue;
+        }
+
+        if let Some(height) = WaveHeight::to_wave_height(word) {
+            phrases.push(WavePhrase {
+                qualifiers: mem::take(&mut qualifiers),
+                area: area.clone(),
+                height,
+            });
+        } else if word == "うねり" {
+            swell = true;
+        } else if let Some(qualifier) = Qualifier::to_qualifier(word) {
+            qualifiers.push(qualifier);
+        } else if !matches!(word.as_str(), "を伴う" | "ただし") {
+            others.push(word.clone());
+        }
+        i += 1;
+    }
+
+    WaveForecast {
+        phrases,
+        swell,
+        others,
+    }
+}
+
+/// Weather condition in forecast text.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum WeatherCondition {
+    /// 晴れ
+    Clear,
+    /// くもり
+    Cloudy,
+    /// 雨
+    Rain,
+    /// 雪
+    Snow,
+    /// 雨か雪
+    RainOrSnow,
+    /// 雪か雨
+    SnowOrRain,
+    /// みぞれ
+    Sleet,
+    /// 雷
+    Thunder,
+    /// 霧
+    Fog,
+    /// 大雨
+    HeavyRain,
+    /// 大雪
+    HeavySnow,
+    /// 暴風雨
+    Rainstorm,
+    /// 暴風雪, ふぶき
+    Snowstorm,
+}
+
+impl WeatherCondition {
+    /// convert str to WeatherCondition.
+    pub fn to_weather_condition(word: &str) -> Option<WeatherCondition> {
+        match word {
+            "晴れ" | "晴" => Some(WeatherCondition::Clear),
+            "くもり" | "曇り" | "曇" => Some(WeatherCondition::Cloudy),
+            "雨" => Some(WeatherCondition::Rain),
+            "雪" => Some(WeatherCondition::Snow),
+            "雨か雪" => Some(WeatherCondition::RainOrSnow),
+            "雪か雨" => Some(WeatherCondition::SnowOrRain),
+            "みぞれ" => Some(WeatherCondition::Sleet),
+            "雷" => Some(WeatherCondition::Thunder),
+            "霧" => Some(WeatherCondition::Fog),
+            "大雨" => Some(WeatherCondition::HeavyRain),
+            "大雪" => Some(WeatherCondition::HeavySnow),
+            "暴風雨" => Some(WeatherCondition::Rainstorm),
+            "暴風雪" | "ふぶき" | "吹雪" => Some(WeatherCondition::Snowstorm),
+            _ => None,
+        }
+    }
+
+    /// Returns the English name.
+    pub fn english(&self) -> &'static str {
+        match self {
+            WeatherCondition::Clear => "sunny",
+            WeatherCondition::Cloudy => "cloudy",
+            WeatherCondition::Rain => "rain",
+            WeatherCondition::Snow => "snow",
+            WeatherCondition::RainOrSnow => "rain or snow",
+            WeatherCondition::SnowOrRain => "snow or rain",
+            WeatherCondition::Sleet => "sleet",
+            WeatherCondition::Thunder => "thunder",
+            WeatherCondition::Fog => "fog",
+            WeatherCondition::HeavyRain => "heavy rain",
+            WeatherCondition::HeavySnow => "heavy snow",
+            WeatherCondition::Rainstorm => "rainstorm",
+            WeatherCondition::Snowstorm => "snowstorm",
+        }
+    }
+
+    /// Returns true if the condition is rain or snow.
+    pub fn is_precipitation(&self) -> bool {
+        !matches!(
+            self,
+            WeatherCondition::Clear
+                | WeatherCondition::Cloudy
+                | WeatherCondition::Thunder
+                | WeatherCondition::Fog
+        )
+    }
+}
+
+/// Intensity of precipitation.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Intensity {
+    /// 強く
+    Strong,
+    /// 激しく
+    Heavy,
+    /// 非常に激しく
+    VeryHeavy,
+}
+
+impl Intensity {
+    /// convert str to Intensity.
+    pub fn to_intensity(word: &str) -> Option<Intensity> {
+        match word {
+            "強く" => Some(Intensity::Strong),
+            "激しく" => Some(Intensity::Heavy),
+            "非常に激しく" => Some(Intensity::VeryHeavy),
+            _ => None,
+        }
+    }
+
+    /// Returns the English word.
+    pub fn english(&self) -> &'static str {
+        match self {
+            Intensity::Strong => "strong",
+            Intensity::Heavy => "heavy",
+            Intensity::VeryHeavy => "very heavy",
+        }
+    }
+}
+
+/// Time window such as "昼過ぎ　から　夜のはじめ頃".
+///
+/// `from` and `until` are the same for a single time of day.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct TimeWindow {
+    pub from: Option<TimeOfDay>,
+    pub until: Option<TimeOfDay>,
+}
+
+impl TimeWindow {
+    /// Returns the English phrase.
+    pub fn english(&self) -> String {
+        match (self.from, self.until) {
+            (Some(from), Some(until)) if from == until => format!("in the {}", from.english()),
+            (Some(from), Some(until)) => {
+                format!("from {} until {}", from.english(), until.english())
+            }
+            (Some(from), None) => format!("from {}", from.english()),
+            (None, Some(until)) => format!("until {}", until.english()),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// A weather condition with its qualifiers.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WeatherPhrase {
+    pub qualifiers: Vec<Qualifier>,
+    pub time: Option<TimeWindow>,
+    /// An area such as "山沿い".
+    pub area: Option<String>,
+    pub condition: WeatherCondition,
+    pub intensity: Option<Intensity>,
+    /// を伴う: the condition accompanies the previous one.
+    pub accompanying: bool,
+}
+
+/// Parsed weather text.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WeatherText {
+    pub phrases: Vec<WeatherPhrase>,
+    /// Words that could not be parsed.
+    pub others: Vec<String>,
+}
+
+/// English names of the areas frequently used in forecast text.
+const AREA_ENGLISH: [(&str, &str); 6] = [
+    ("山沿い", "mountain areas"),
+    ("山地", "mountains"),
+    ("平地", "plains"),
+    ("海上", "at sea"),
+    ("沿岸", "coastal areas"),
+    ("沿岸部", "coastal areas"),
+];
+
+impl WeatherText {
+    /// Render the weather text in English.
+    ///
+    /// e.g. "Cloudy, locally snow from afternoon until early night".
+    pub fn to_english(&self) -> String {
+        let mut sentence = Vec::new();
+        for phrase in &self.phrases {
+            let mut words = Vec::new();
+            for qualifier in &phrase.qualifiers {
+                words.push(qualifier.english().to_string());
+            }
+            if phrase.accompanying {
+                words.push("with".to_string());
+            }
+            if let Some(intensity) = phrase.intensity {
+                words.push(intensity.english().to_string());
+            }
+            words.push(phrase.condition.english().to_string());
+            if let Some(time) = &phrase.time {
+                words.push(time.english());
+            }
+            if let Some(area) = &phrase.area {
+                match AREA_ENGLISH.iter().find(|(jp, _)| jp == area) {
+                    Some((_, "at sea")) => words.push("at sea".to_string()),
+                    Some((_, en)) => words.push(format!("in {}", en)),
+                    None => words.push(format!("in {}", area)),
+                }
+            }
+            sentence.push(words.join(" "));
+        }
+
+        let mut english = sentence.join(", ");
+        if let Some(first) = english.get(0..1) {
+            english = first.to_uppercase() + &english[1..];
+        }
+        english
+    }
+}
+
+/// Parse weather text such as "くもり　所により　昼過ぎ　から　よるのはじめ頃　雪".
+pub fn parse_weather(text: &str) -> WeatherText {
+    let mut words = tokens(text);
+    // "非常に　激しく" is a single intensity.
+    while let Some(i) = words
+        .windows(2)
+        .position(|w| w[0] == "非常に" && w[1] == "激しく")
+    {
+        words.splice(i..i + 2, ["非常に激しく".to_string()]);
+    }
+    let mut phrases: Vec<WeatherPhrase> = Vec::new();
+    let mut others = Vec::new();
+    let mut qualifiers = Vec::new();
+    let mut time: Option<TimeWindow> = None;
+    let mut area = None;
+    let mut intensity = None;
+    let mut after_from = false;
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = &words[i];
+        if words.get(i + 1).is_some_and(|w| w == "では") {
+            area = Some(word.clone());
+            i += 2;
+            continue;
+        }
+
+        if let Some(condition) = WeatherCondition::to_weather_condition(word) {
+            phrases.push(WeatherPhrase {
+                qualifiers: mem::take(&mut qualifiers),
+                time: time.take(),
+                area: area.take(),
+                condition,
+                intensity: intensity.take(),
+                accompanying: false,
+            });
+        } else if let Some(t) = TimeOfDay::to_time_of_day(word) {
+            time = match time {
+                Some(window) if after_from => Some(TimeWindow {
+                    until: Some(t),
+                    ..window
+                }),
+                _ => Some(TimeWindow {
+                    from: Some(t),
+                    until: Some(t),
+                }),
+            };
+        } else if word == "から" {
+            if let Some(window) = time.as_mut() {
+                window.until = None;
+            }
+        } else if word == "まで" {
+            if let Some(window) = time.as_mut() {
+                window.from = None;
+            }
+        } else if let Some(qualifier) = Qualifier::to_qualifier(word) {
+            qualifiers.push(qualifier);
+        } else if let Some(level) = Intensity::to_intensity(word) {
+            // "雨　雷　を伴い　激しく　降る": the intensity is of the precipitation,
+            // not of the condition accompanying it.
+            let main = phrases
+                .iter_mut()
+                .rev()
+                .find(|p| !p.accompanying && p.condition.is_precipitation());
+            match main {
+                Some(main) if main.intensity.is_none() => main.intensity = Some(level),
+                _ => intensity = Some(level),
+            }
+        } else if matches!(word.as_str(), "を伴う" | "を伴い") {
+            if let Some(last) = phrases.last_mut() {
+                last.accompanying = true;
+            }
+        } else if !matches!(word.as_str(), "は" | "降る" | "非常に") {
+            others.push(word.clone());
+        }
+        after_from = word == "から";
+        i += 1;
+    }
+
+    WeatherText { phrases, others }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_full_width() {
+        assert_eq!(normalize("１．５メートル　後"), "1.5メートル 後");
+    }
+
+    #[test]
+    fn wave_height() {
+        let range = Some(WaveHeight {
+            min_m: 1.0,
+            max_m: 1.5,
+        });
+        assert_eq!(WaveHeight::to_wave_height("１～１．５メートル"), range);
+        assert_eq!(WaveHeight::to_wave_height("１〜１．５メートル"), range);
+        assert_eq!(WaveHeight::to_wave_height("１から１．５メートル"), range);
+        assert_eq!(
+            WaveHeight::to_wave_height("１メートルから１．５メートル"),
+            range
+        );
+        assert_eq!(
+            WaveHeight::to_wave_height("０．５メートル"),
+            Some(WaveHeight {
+                min_m: 0.5,
+                max_m: 0.5
+            })
+        );
+        assert_eq!(WaveHeight::to_wave_height("１．５"), None);
+    }
+
+    #[test]
+    fn wind() {
+        let wind = parse_wind("西の風　はじめ　やや強く　海上　では　西の風　強く");
+        assert_eq!(
+            wind.sections[0].phrases,
+            vec![
+                WindPhrase {
+                    qualifiers: vec![],
+                    direction: Some(WindDirection::W),
+                    strength: None
+                },
+                WindPhrase {
+                    qualifiers: vec![Qualifier::AtFirst],
+                    direction: Some(WindDirection::W),
+                    strength: Some(WindStrength::SomewhatStrong),
+                },
+            ]
+        );
+        assert_eq!(
+            wind.sections[1].phrases,
+            vec![WindPhrase {
+                qualifiers: vec![],
+                direction: Some(WindDirection::W),
+                strength: Some(WindStrength::Strong),
+            }]
+        );
+        assert_eq!(wind.max_strength(), Some(WindStrength::Strong));
+
+        let wind = parse_wind("北よりの風　日中　南の風　三陸沖　では　後　北東の風　非常に強く");
+        assert_eq!(
+            wind.sections[0].phrases[0].direction,
+            Some(WindDirection::N)
+        );
+        assert_eq!(
+            wind.sections[0].phrases[1].qualifiers,
+            vec![Qualifier::Time(TimeOfDay::Daytime)]
+        );
+        assert_eq!(wind.sections[1].area, WindArea::Named("三陸沖".to_string()));
+        assert_eq!(
+            wind.sections[1].phrases[0].qualifiers,
+            vec![Qualifier::Later]
+        );
+        assert_eq!(wind.max_strength(), Some(WindStrength::VeryStrong));
+        assert!(wind.others.is_empty());
+    }
+
+    #[test]
+    fn wave() {
+        let wave = parse_wave("２．５メートル　後　１．５メートル　うねり　を伴う");
+        assert_eq!(wave.phrases.len(), 2);
+        assert_eq!(wave.phrases[1].qualifiers, vec![Qualifier::Later]);
+        assert_eq!(
+            wave.phrases[1].height,
+            WaveHeight {
+                min_m: 1.5,
+                max_m: 1.5
+            }
+        );
+        assert!(wave.swell);
+        assert_eq!(wave.max_m(), Some(2.5));
+
+        let wave = parse_wave("０．５メートル　ただし　三陸沖　では　１から１．５メートル");
+        assert_eq!(wave.phrases[1].area, Some("三陸沖".to_string()));
+        assert_eq!(
+            wave.phrases[1].height,
+            WaveHeight {
+                min_m: 1.0,
+                max_m: 1.5
+            }
+        );
+        assert!(wave.others.is_empty());
+
+        assert_eq!(parse_wave("").max_m(), None);
+    }
+
+    #[test]
+    fn weather() {
+        let weather = parse_weather("くもり　所により　昼過ぎ　から　よるのはじめ頃　雪");
+        assert_eq!(weather.phrases.len(), 2);
+        assert_eq!(weather.phrases[0].condition, WeatherCondition::Cloudy);
+        assert_eq!(weather.phrases[1].qualifiers, vec![Qualifier::Locally]);
+        assert_eq!(
+            weather.phrases[1].time,
+            Some(TimeWindow {
+                from: Some(TimeOfDay::Afternoon),
+                until: Some(TimeOfDay::EarlyNight)
+            })
+        );
+        assert_eq!(weather.phrases[1].condition, WeatherCondition::Snow);
+        assert!(weather.others.is_empty());
+        assert_eq!(
+            weather.to_english(),
+            "Cloudy, locally snow from afternoon until early night"
+        );
+
+        let weather = parse_weather("くもり　時々　雪か雨");
+        assert_eq!(weather.to_english(), "Cloudy, occasionally snow or rain");
+
+        let weather = parse_weather("雨　夜　は　所により　雷　を伴い　激しく　降る");
+        assert_eq!(
+            weather.phrases[1].time,
+            Some(TimeWindow {
+                from: Some(TimeOfDay::Night),
+                until: Some(TimeOfDay::Night)
+            })
+        );
+        assert!(weather.phrases[1].accompanying);
+        assert_eq!(weather.phrases[0].intensity, Some(Intensity::Heavy));
+        assert_eq!(weather.phrases[1].intensity, None);
+        assert!(weather.others.is_empty());
+        assert_eq!(
+            weather.to_english(),
+            "Heavy rain, locally with thunder in the night"
+        );
+
+        let weather = parse_weather("くもり　時々　雨　所により　雷　を伴い　非常に　激しく　降る");
+        assert_eq!(weather.phrases[1].condition, WeatherCondition::Rain);
+        assert_eq!(weather.phrases[1].intensity, Some(Intensity::VeryHeavy));
+        assert_eq!(weather.phrases[2].intensity, None);
+        assert_eq!(
+            weather.to_english(),
+            "Cloudy, occasionally very heavy rain, locally with thunder"
+        );
+
+        let weather = parse_weather("晴れ　夕方　から　くもり　山沿い　では　夜遅く　雨");
+        assert_eq!(
+            weather.phrases[1].time,
+            Some(TimeWindow {
+                from: Some(TimeOfDay::Evening),
+                until: None
+            })
+        );
+        assert_eq!(
+            weather.to_english(),
+            "Sunny, cloudy from evening, rain in the late night in mountain areas"
+        );
+    }
+}
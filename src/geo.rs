@@ -0,0 +1,470 @@
+//! # Area Boundaries
+//!
+//! Point-in-area lookup with the boundary data of the forecast areas (GeoJSON or TopoJSON
+//! converted from JMA's GIS data and supplied as a local file). Enabled by the `geo` feature.
+//!
+//! Each feature is a Polygon or MultiPolygon whose property (`code` by default) is the area code
+//! of a class (Class10, Class15 or Class20). Coordinates are longitude and latitude in degrees.
+//!
+//! ```json
+//! {
+//!   "type": "FeatureCollection",
+//!   "features": [
+//!     {
+//!       "type": "Feature",
+//!       "properties": { "code": "1020100", "name": "前橋市" },
+//!       "geometry": { "type": "Polygon", "coordinates": [[[139.0, 36.3], [139.2, 36.3], ...]] }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::area::{Areas, JmaAreaClass};
+//! use jma::geo::{AreaBoundaries, DEFAULT_CODE_PROPERTY};
+//!
+//! let boundaries =
+//!     AreaBoundaries::from_file("class20.geojson", JmaAreaClass::Class20, DEFAULT_CODE_PROPERTY)
+//!         .unwrap();
+//! let areas = Areas::from_reader(std::fs::File::open("area.json").unwrap()).unwrap();
+//! // From the Center down to the Class20 containing the point
+//! for area in boundaries.locate_area(&areas, 36.39, 139.06).unwrap() {
+//!     println!("{} {} {}", area.class, area.code, area.area.name);
+//! }
+//! ```
+
+use crate::area::{Area, Areas, JmaAreaClass};
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum GeoError {
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    InvalidGeometry(String),
+}
+
+impl std::fmt::Display for GeoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeoError::IoError(e) => write!(f, "IO error: {}", e),
+            GeoError::JsonError(e) => write!(f, "JSON error: {}", e),
+            GeoError::InvalidGeometry(e) => write!(f, "Invalid geometry: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GeoError {}
+
+impl From<std::io::Error> for GeoError {
+    fn from(err: std::io::Error) -> GeoError {
+        GeoError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for GeoError {
+    fn from(err: serde_json::Error) -> GeoError {
+        GeoError::JsonError(err)
+    }
+}
+
+/// Property holding the area code.
+pub const DEFAULT_CODE_PROPERTY: &str = "code";
+
+/// (longitude, latitude)
+type Point = (f64, f64);
+
+/// A polygon: the exterior ring followed by the holes.
+#[derive(Debug, Clone)]
+struct Polygon {
+    rings: Vec<Vec<Point>>,
+}
+
+impl Polygon {
+    fn contains(&self, point: Point) -> bool {
+        let mut rings = self.rings.iter();
+        match rings.next() {
+            Some(exterior) if ring_contains(exterior, point) => {
+                !rings.any(|hole| ring_contains(hole, point))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Ray casting.
+fn ring_contains(ring: &[Point], (x, y): Point) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for (i, &(xi, yi)) in ring.iter().enumerate() {
+        let (xj, yj) = ring[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[derive(Debug, Clone)]
+struct Boundary {
+    code: String,
+    polygons: Vec<Polygon>,
+    /// (min lon, min lat, max lon, max lat)
+    bbox: (f64, f64, f64, f64),
+}
+
+impl Boundary {
+    fn new(code: String, polygons: Vec<Polygon>) -> Boundary {
+        let mut bbox = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        for &(x, y) in polygons.iter().flat_map(|p| p.rings.first()).flatten() {
+            bbox = (bbox.0.min(x), bbox.1.min(y), bbox.2.max(x), bbox.3.max(y));
+        }
+        Boundary {
+            code,
+            polygons,
+            bbox,
+        }
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        let (x, y) = point;
+        let (min_x, min_y, max_x, max_y) = self.bbox;
+        (min_x..=max_x).contains(&x)
+            && (min_y..=max_y).contains(&y)
+            && self.polygons.iter().any(|p| p.contains(point))
+    }
+}
+
+fn invalid(message: &str) -> GeoError {
+    GeoError::InvalidGeometry(message.to_string())
+}
+
+fn to_point(value: &Value) -> Result<Point, GeoError> {
+    match (value[0].as_f64(), value[1].as_f64()) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        _ => Err(invalid("position is not [lon, lat]")),
+    }
+}
+
+fn to_polygon(rings: &Value) -> Result<Polygon, GeoError> {
+    let rings = rings
+        .as_array()
+        .ok_or_else(|| invalid("polygon is not an array"))?
+        .iter()
+        .map(|ring| {
+            ring.as_array()
+                .ok_or_else(|| invalid("ring is not an array"))?
+                .iter()
+                .map(to_point)
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Polygon { rings })
+}
+
+/// Polygons of a GeoJSON geometry. Other geometry types have no area.
+fn geojson_polygons(geometry: &Value) -> Result<Vec<Polygon>, GeoError> {
+    match geometry["type"].as_str() {
+        Some("Polygon") => Ok(vec![to_polygon(&geometry["coordinates"])?]),
+        Some("MultiPolygon") => geometry["coordinates"]
+            .as_array()
+            .ok_or_else(|| invalid("MultiPolygon is not an array"))?
+            .iter()
+            .map(to_polygon)
+            .collect(),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Decode the arcs of a TopoJSON topology into positions.
+fn topojson_arcs(topology: &Value) -> Result<Vec<Vec<Point>>, GeoError> {
+    let transform = &topology["transform"];
+    let scale = to_point(&transform["scale"]).ok();
+    let translate = to_point(&transform["translate"]).unwrap_or((0.0, 0.0));
+    topology["arcs"]
+        .as_array()
+        .ok_or_else(|| invalid("arcs not found"))?
+        .iter()
+        .map(|arc| {
+            let mut position = (0.0, 0.0);
+            arc.as_array()
+                .ok_or_else(|| invalid("arc is not an array"))?
+                .iter()
+                .map(|value| {
+                    let point = to_point(value)?;
+                    Ok(match scale {
+                        // Quantized and delta-encoded
+                        Some((sx, sy)) => {
+                            position = (position.0 + point.0, position.1 + point.1);
+                            (position.0 * sx + translate.0, position.1 * sy + translate.1)
+                        }
+                        None => point,
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A ring of TopoJSON arc indexes; a negative index `!i` is the arc `i` reversed.
+fn topojson_ring(arcs: &[Vec<Point>], indexes: &Value) -> Result<Vec<Point>, GeoError> {
+    let mut ring: Vec<Point> = Vec::new();
+    for index in indexes
+        .as_array()
+        .ok_or_else(|| invalid("ring is not an array"))?
+    {
+        let index = index
+            .as_i64()
+            .ok_or_else(|| invalid("arc index is not an integer"))?;
+        let (i, reversed) = if index < 0 {
+            (!index as usize, true)
+        } else {
+            (index as usize, false)
+        };
+        let arc = arcs
+            .get(i)
+            .ok_or_else(|| invalid("arc index out of range"))?;
+        let mut points = arc.clone();
+        if reversed {
+            points.reverse();
+        }
+        // Consecutive arcs share the end points.
+        let skip = usize::from(!ring.is_empty());
+        ring.extend(points.into_iter().skip(skip));
+    }
+    Ok(ring)
+}
+
+fn topojson_polygon(arcs: &[Vec<Point>], rings: &Value) -> Result<Polygon, GeoError> {
+    let rings = rings
+        .as_array()
+        .ok_or_else(|| invalid("polygon is not an array"))?
+        .iter()
+        .map(|ring| topojson_ring(arcs, ring))
+        .collect::<Result<_, _>>()?;
+    Ok(Polygon { rings })
+}
+
+fn topojson_polygons(arcs: &[Vec<Point>], geometry: &Value) -> Result<Vec<Polygon>, GeoError> {
+    match geometry["type"].as_str() {
+        Some("Polygon") => Ok(vec![topojson_polygon(arcs, &geometry["arcs"])?]),
+        Some("MultiPolygon") => geometry["arcs"]
+            .as_array()
+            .ok_or_else(|| invalid("MultiPolygon is not an array"))?
+            .iter()
+            .map(|polygon| topojson_polygon(arcs, polygon))
+            .collect(),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// The code of the feature; numeric codes are zero-padded to the width of the class
+/// (e.g. 110000 to "0110000" in Class20).
+fn code_of(properties: &Value, code_property: &str, class: &JmaAreaClass) -> Option<String> {
+    let width = match class {
+        JmaAreaClass::Class20 => 7,
+        _ => 6,
+    };
+    match &properties[code_property] {
+        Value::String(code) => Some(code.clone()),
+        Value::Number(code) => Some(format!("{:0width$}", code.as_u64()?, width = width)),
+        _ => None,
+    }
+}
+
+/// Boundaries of the areas in a class.
+#[derive(Debug, Clone)]
+pub struct AreaBoundaries {
+    pub class: JmaAreaClass,
+    boundaries: Vec<Boundary>,
+}
+
+impl AreaBoundaries {
+    /// Load a GeoJSON FeatureCollection.
+    pub fn from_geojson(
+        json: &Value,
+        class: JmaAreaClass,
+        code_property: &str,
+    ) -> Result<AreaBoundaries, GeoError> {
+        let features = json["features"]
+            .as_array()
+            .ok_or_else(|| invalid("features not found"))?;
+        let mut boundaries = Vec::new();
+        for feature in features {
+            if let Some(code) = code_of(&feature["properties"], code_property, &class) {
+                boundaries.push(Boundary::new(code, geojson_polygons(&feature["geometry"])?));
+            }
+        }
+        Ok(AreaBoundaries { class, boundaries })
+    }
+
+    /// Load the geometries of all the objects in a TopoJSON topology.
+    pub fn from_topojson(
+        json: &Value,
+        class: JmaAreaClass,
+        code_property: &str,
+    ) -> Result<AreaBoundaries, GeoError> {
+        let arcs = topojson_arcs(json)?;
+        let objects = json["objects"]
+            .as_object()
+            .ok_or_else(|| invalid("objects not found"))?;
+        let mut boundaries = Vec::new();
+        for object in objects.values() {
+            let geometries = match object["geometries"].as_array() {
+                Some(geometries) => geometries.iter().collect(),
+                None => vec![object],
+            };
+            for geometry in geometries {
+                if let Some(code) = code_of(&geometry["properties"], code_property, &class) {
+                    boundaries.push(Boundary::new(code, topojson_polygons(&arcs, geometry)?));
+                }
+            }
+        }
+        Ok(AreaBoundaries { class, boundaries })
+    }
+
+    /// Load a GeoJSON or TopoJSON file.
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        class: JmaAreaClass,
+        code_property: &str,
+    ) -> Result<AreaBoundaries, GeoError> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let json: Value = serde_json::from_reader(file)?;
+        match json["type"].as_str() {
+            Some("Topology") => AreaBoundaries::from_topojson(&json, class, code_property),
+            _ => AreaBoundaries::from_geojson(&json, class, code_property),
+        }
+    }
+
+    /// Number of the areas.
+    pub fn len(&self) -> usize {
+        self.boundaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boundaries.is_empty()
+    }
+
+    /// Returns the code of the area containing the point.
+    pub fn locate(&self, lat: f64, lon: f64) -> Option<&str> {
+        self.boundaries
+            .iter()
+            .find(|b| b.contains((lon, lat)))
+            .map(|b| b.code.as_str())
+    }
+
+    /// Returns the areas from the Center down to the area containing the point.
+    pub fn locate_area(&self, areas: &Areas, lat: f64, lon: f64) -> Option<Vec<Area>> {
+        let area = areas.values(&self.class, self.locate(lat, lon)?)?;
+        Some(areas.path(&area))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64, size: f64) -> Value {
+        serde_json::json!([[
+            [x, y],
+            [x + size, y],
+            [x + size, y + size],
+            [x, y + size],
+            [x, y]
+        ]])
+    }
+
+    #[test]
+    fn geojson() {
+        let mut with_hole = square(139.0, 36.0, 1.0);
+        with_hole
+            .as_array_mut()
+            .unwrap()
+            .push(square(139.4, 36.4, 0.2)[0].clone());
+        let json = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {"code": "1020100"},
+                 "geometry": {"type": "Polygon", "coordinates": with_hole}},
+                {"type": "Feature", "properties": {"code": "1020300"},
+                 "geometry": {"type": "MultiPolygon", "coordinates": [square(139.4, 36.4, 0.2), square(141.0, 36.0, 1.0)]}},
+                {"type": "Feature", "properties": {"name": "no code"},
+                 "geometry": {"type": "Polygon", "coordinates": square(0.0, 0.0, 1.0)}}
+            ]
+        });
+        let boundaries =
+            AreaBoundaries::from_geojson(&json, JmaAreaClass::Class20, DEFAULT_CODE_PROPERTY)
+                .unwrap();
+        assert_eq!(boundaries.len(), 2);
+        assert_eq!(boundaries.locate(36.1, 139.1), Some("1020100"));
+        assert_eq!(boundaries.locate(36.5, 139.5), Some("1020300"));
+        assert_eq!(boundaries.locate(36.5, 141.5), Some("1020300"));
+        assert_eq!(boundaries.locate(36.5, 140.5), None);
+
+        let areas = Areas::from_json_str(include_str!("../testdata/area.json")).unwrap();
+        let path = boundaries.locate_area(&areas, 36.1, 139.1).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[1].area.name, "群馬県");
+        assert_eq!(path[4].area.name, "前橋市");
+
+        // Numeric codes lose the leading zero in JSON.
+        let numeric = |code: Value| {
+            serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [
+                    {"type": "Feature", "properties": {"code": code},
+                     "geometry": {"type": "Polygon", "coordinates": square(141.0, 43.0, 1.0)}},
+                    {"type": "Feature", "properties": {"code": 1.5},
+                     "geometry": {"type": "Polygon", "coordinates": square(0.0, 0.0, 1.0)}}
+                ]
+            })
+        };
+        let boundaries = AreaBoundaries::from_geojson(
+            &numeric(Value::from(110000)),
+            JmaAreaClass::Class20,
+            DEFAULT_CODE_PROPERTY,
+        )
+        .unwrap();
+        assert_eq!(boundaries.len(), 1);
+        assert_eq!(boundaries.locate(43.1, 141.3), Some("0110000"));
+        let boundaries = AreaBoundaries::from_geojson(
+            &numeric(Value::from(16000)),
+            JmaAreaClass::Office,
+            DEFAULT_CODE_PROPERTY,
+        )
+        .unwrap();
+        assert_eq!(boundaries.locate(43.1, 141.3), Some("016000"));
+    }
+
+    #[test]
+    fn topojson() {
+        // Two unit squares sharing the arc 0 (x = 1), quantized by 0.5 and translated.
+        let json = serde_json::json!({
+            "type": "Topology",
+            "transform": {"scale": [0.5, 0.5], "translate": [139.0, 36.0]},
+            "arcs": [
+                [[2, 0], [0, 2]],
+                [[2, 2], [-2, 0], [0, -2], [2, 0]],
+                [[2, 0], [2, 0], [0, 2], [-2, 0]]
+            ],
+            "objects": {
+                "class20": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        {"type": "Polygon", "arcs": [[0, 1]], "properties": {"code": 1020100}},
+                        {"type": "Polygon", "arcs": [[2, -1]], "properties": {"code": "1020300"}}
+                    ]
+                }
+            }
+        });
+        let boundaries =
+            AreaBoundaries::from_topojson(&json, JmaAreaClass::Class20, DEFAULT_CODE_PROPERTY)
+                .unwrap();
+        assert_eq!(boundaries.locate(36.5, 139.5), Some("1020100"));
+        assert_eq!(boundaries.locate(36.5, 140.5), Some("1020300"));
+        assert_eq!(boundaries.locate(37.5, 139.5), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! # Embedded Weather Icons
+//!
+//! Weather icons bundled in the crate so they can be rendered without
+//! hotlinking JMA. Enabled by the `icons` feature.
+//!
+//! - AMeDAS symbol bitmaps (`img/{code}.bmp`) for the automatic observed weather codes 0 to 16.
+//! - Forecast SVGs (`img/svg/{code}.svg`) for the JMA weather codes used by
+//!   [`AMEDAS_WEATHER_JMA_WEATHER_CODES`](crate::amedas::AMEDAS_WEATHER_JMA_WEATHER_CODES).
+//!   These are a redistributable equivalent set drawn for this crate, not JMA's own images.
+//!   Only the base codes are drawn; any other code uses the icon of its base code
+//!   (e.g. 101 晴時々曇 uses 100 晴, 311 雨後晴 uses 300 雨).
+//!
+//! ## Example
+//! ```rust
+//! use jma::icon::{amedas_symbol_bmp, weather_svg};
+//!
+//! // AMeDAS weather code 7 (rain) observed in the daytime.
+//! let bmp = amedas_symbol_bmp(7).unwrap();
+//! assert_eq!(&bmp[0..2], b"BM");
+//! let svg = weather_svg(7, false);
+//! assert!(svg.starts_with(b"<svg"));
+//! ```
+
+use crate::amedas::AMEDAS_WEATHER_JMA_WEATHER_CODES;
+
+/// AMeDAS symbol bitmaps indexed by the automatic observed weather code.
+pub const AMEDAS_SYMBOL_BMP: [&[u8]; 17] = [
+    include_bytes!("../img/0.bmp"),
+    include_bytes!("../img/1.bmp"),
+    include_bytes!("../img/2.bmp"),
+    include_bytes!("../img/3.bmp"),
+    include_bytes!("../img/4.bmp"),
+    include_bytes!("../img/5.bmp"),
+    include_bytes!("../img/6.bmp"),
+    include_bytes!("../img/7.bmp"),
+    include_bytes!("../img/8.bmp"),
+    include_bytes!("../img/9.bmp"),
+    include_bytes!("../img/10.bmp"),
+    include_bytes!("../img/11.bmp"),
+    include_bytes!("../img/12.bmp"),
+    include_bytes!("../img/13.bmp"),
+    include_bytes!("../img/14.bmp"),
+    include_bytes!("../img/15.bmp"),
+    include_bytes!("../img/16.bmp"),
+];
+
+/// Forecast SVGs keyed by the JMA weather code.
+pub const JMA_WEATHER_SVG: [(u32, &[u8]); 9] = [
+    (100, include_bytes!("../img/svg/100.svg")),
+    (200, include_bytes!("../img/svg/200.svg")),
+    (300, include_bytes!("../img/svg/300.svg")),
+    (302, include_bytes!("../img/svg/302.svg")),
+    (308, include_bytes!("../img/svg/308.svg")),
+    (400, include_bytes!("../img/svg/400.svg")),
+    (402, include_bytes!("../img/svg/402.svg")),
+    (403, include_bytes!("../img/svg/403.svg")),
+    (500, include_bytes!("../img/svg/500.svg")),
+];
+
+/// Returns the AMeDAS symbol bitmap of the automatic observed weather code.
+pub fn amedas_symbol_bmp(code: u32) -> Option<&'static [u8]> {
+    AMEDAS_SYMBOL_BMP.get(code as usize).copied()
+}
+
+/// Returns the SVG of the JMA weather code.
+///
+/// A code without its own SVG falls back to its base code (the hundreds, e.g. 201 to 200).
+pub fn forecast_svg(jma_code: u32) -> Option<&'static [u8]> {
+    let svg = |code: u32| {
+        JMA_WEATHER_SVG
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, svg)| *svg)
+    };
+    svg(jma_code).or_else(|| svg(jma_code / 100 * 100))
+}
+
+/// Returns the SVG of the automatic observed weather code.
+///
+/// This is the embedded counterpart of [`svg_url`](crate::amedas::svg_url);
+/// an unknown code falls back to the same image as code 999.
+pub fn weather_svg(code: u32, night: bool) -> &'static [u8] {
+    let jma_code = |c: u32| {
+        AMEDAS_WEATHER_JMA_WEATHER_CODES
+            .iter()
+            .find(|(amedas, _, _)| *amedas == c)
+            .map(|(_, day, night_code)| if night { *night_code } else { *day })
+    };
+    let svg_code = jma_code(code).or_else(|| jma_code(999)).unwrap();
+    forecast_svg(svg_code).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bmp_for_every_code() {
+        for code in 0..=16 {
+            let bmp = amedas_symbol_bmp(code).unwrap();
+            assert_eq!(&bmp[0..2], b"BM");
+        }
+        assert!(amedas_symbol_bmp(17).is_none());
+    }
+
+    #[test]
+    fn svg_for_every_code() {
+        for (code, _day_svg, _night_svg) in AMEDAS_WEATHER_JMA_WEATHER_CODES {
+            assert!(weather_svg(code, false).starts_with(b"<svg"));
+            assert!(weather_svg(code, true).starts_with(b"<svg"));
+        }
+        // Undefined code
+        assert_eq!(weather_svg(200, true), forecast_svg(308).unwrap());
+        assert_eq!(weather_svg(0, true), forecast_svg(500).unwrap());
+    }
+
+    #[test]
+    fn svg_of_base_code() {
+        assert_eq!(forecast_svg(302), Some(JMA_WEATHER_SVG[3].1));
+        assert_eq!(forecast_svg(101), forecast_svg(100));
+        assert_eq!(forecast_svg(110), forecast_svg(100));
+        assert_eq!(forecast_svg(201), forecast_svg(200));
+        assert_eq!(forecast_svg(311), forecast_svg(300));
+        assert_eq!(forecast_svg(450), forecast_svg(400));
+        assert!(forecast_svg(999).is_none());
+        assert!(forecast_svg(0).is_none());
+    }
+}
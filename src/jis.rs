@@ -0,0 +1,259 @@
+//! # Municipality and Prefecture Codes
+//!
+//! Class20 codes embed the local government code (全国地方公共団体コード, JIS X 0402).
+//!
+//! | Code | Example | |
+//! | :--- | :--- | :--- |
+//! | Class20 | `1020100` | 5-digit municipality code + 2-digit part |
+//! | JIS X 0402 | `10201` | prefecture (2) + municipality (3) |
+//! | JIS X 0402 with check digit | `102016` | |
+//! | Office | `100000` | prefecture (2) + 4 digits |
+//!
+//! JMA splits some municipalities into several Class20 areas (e.g. `2020201` and `2020202`),
+//! and a designated city (政令指定都市) is a single Class20 area while JIS has a code per ward.
+//!
+//! ## Example
+//! ```rust
+//! use jma::jis::{check_digit, jis_from_class20, prefecture_code, prefecture_name};
+//!
+//! assert_eq!(check_digit("10201"), Some(6));
+//! assert_eq!(jis_from_class20("1020100"), Some("102016".to_string()));
+//! assert_eq!(prefecture_code("014030"), Some("01"));
+//! assert_eq!(prefecture_name("01"), Some("北海道"));
+//! ```
+
+use crate::area::{Area, Areas, JmaAreaClass};
+
+/// Prefecture codes and names.
+pub const PREFECTURES: [(&str, &str, &str); 47] = [
+    ("01", "北海道", "Hokkaido"),
+    ("02", "青森県", "Aomori"),
+    ("03", "岩手県", "Iwate"),
+    ("04", "宮城県", "Miyagi"),
+    ("05", "秋田県", "Akita"),
+    ("06", "山形県", "Yamagata"),
+    ("07", "福島県", "Fukushima"),
+    ("08", "茨城県", "Ibaraki"),
+    ("09", "栃木県", "Tochigi"),
+    ("10", "群馬県", "Gunma"),
+    ("11", "埼玉県", "Saitama"),
+    ("12", "千葉県", "Chiba"),
+    ("13", "東京都", "Tokyo"),
+    ("14", "神奈川県", "Kanagawa"),
+    ("15", "新潟県", "Niigata"),
+    ("16", "富山県", "Toyama"),
+    ("17", "石川県", "Ishikawa"),
+    ("18", "福井県", "Fukui"),
+    ("19", "山梨県", "Yamanashi"),
+    ("20", "長野県", "Nagano"),
+    ("21", "岐阜県", "Gifu"),
+    ("22", "静岡県", "Shizuoka"),
+    ("23", "愛知県", "Aichi"),
+    ("24", "三重県", "Mie"),
+    ("25", "滋賀県", "Shiga"),
+    ("26", "京都府", "Kyoto"),
+    ("27", "大阪府", "Osaka"),
+    ("28", "兵庫県", "Hyogo"),
+    ("29", "奈良県", "Nara"),
+    ("30", "和歌山県", "Wakayama"),
+    ("31", "鳥取県", "Tottori"),
+    ("32", "島根県", "Shimane"),
+    ("33", "岡山県", "Okayama"),
+    ("34", "広島県", "Hiroshima"),
+    ("35", "山口県", "Yamaguchi"),
+    ("36", "徳島県", "Tokushima"),
+    ("37", "香川県", "Kagawa"),
+    ("38", "愛媛県", "Ehime"),
+    ("39", "高知県", "Kochi"),
+    ("40", "福岡県", "Fukuoka"),
+    ("41", "佐賀県", "Saga"),
+    ("42", "長崎県", "Nagasaki"),
+    ("43", "熊本県", "Kumamoto"),
+    ("44", "大分県", "Oita"),
+    ("45", "宮崎県", "Miyazaki"),
+    ("46", "鹿児島県", "Kagoshima"),
+    ("47", "沖縄県", "Okinawa"),
+];
+
+/// JIS codes of the designated cities and the last code of their wards.
+///
+/// JMA forecasts a designated city as one Class20 area, e.g. 札幌市 `0110000` for the wards `01101` to `01110`.
+pub const DESIGNATED_CITIES: [(&str, &str); 20] = [
+    ("01100", "01110"), // 札幌市
+    ("04100", "04105"), // 仙台市
+    ("11100", "11110"), // さいたま市
+    ("12100", "12106"), // 千葉市
+    ("14100", "14118"), // 横浜市
+    ("14130", "14137"), // 川崎市
+    ("14150", "14153"), // 相模原市
+    ("15100", "15108"), // 新潟市
+    ("22100", "22103"), // 静岡市
+    ("22130", "22140"), // 浜松市
+    ("23100", "23116"), // 名古屋市
+    ("26100", "26111"), // 京都市
+    ("27100", "27128"), // 大阪市
+    ("27140", "27147"), // 堺市
+    ("28100", "28111"), // 神戸市
+    ("33100", "33104"), // 岡山市
+    ("34100", "34108"), // 広島市
+    ("40100", "40109"), // 北九州市
+    ("40130", "40137"), // 福岡市
+    ("43100", "43105"), // 熊本市
+];
+
+/// Returns the check digit of the 5-digit JIS X 0402 code.
+///
+/// The digits are weighted 6, 5, 4, 3 and 2, and the check digit is the last digit of
+/// 11 minus the sum modulo 11.
+pub fn check_digit(jis5: &str) -> Option<u32> {
+    if jis5.len() != 5 {
+        return None;
+    }
+    let mut sum = 0;
+    for (c, weight) in jis5.chars().zip([6, 5, 4, 3, 2]) {
+        sum += c.to_digit(10)? * weight;
+    }
+    Some((11 - sum % 11) % 10)
+}
+
+/// Returns the 5-digit code if the 5 or 6-digit JIS X 0402 code is valid.
+pub fn normalize_jis(jis: &str) -> Option<&str> {
+    if !jis.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match jis.len() {
+        5 => check_digit(jis).map(|_| jis),
+        6 => {
+            let check = jis[5..].parse::<u32>().ok()?;
+            if check_digit(&jis[..5])? == check {
+                Some(&jis[..5])
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the 6-digit JIS X 0402 code (with the check digit) of the Class20 code.
+pub fn jis_from_class20(class20: &str) -> Option<String> {
+    if class20.len() != 7 || !class20.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let jis5 = &class20[..5];
+    Some(format!("{}{}", jis5, check_digit(jis5)?))
+}
+
+/// Returns the designated city containing the ward, or the code itself.
+fn designated_city(jis5: &str) -> &str {
+    DESIGNATED_CITIES
+        .iter()
+        .find(|(city, last)| *city < jis5 && jis5 <= *last)
+        .map_or(jis5, |(city, _)| city)
+}
+
+/// Returns the Class20 areas of the 5 or 6-digit JIS X 0402 code, ordered by code.
+///
+/// A municipality split by JMA returns all its parts; a ward of a designated city returns the city.
+pub fn class20_from_jis(areas: &Areas, jis: &str) -> Vec<Area> {
+    let jis5 = match normalize_jis(jis) {
+        Some(jis5) => designated_city(jis5),
+        None => return Vec::new(),
+    };
+    let mut codes: Vec<&String> = areas
+        .areas(&JmaAreaClass::Class20)
+        .keys()
+        .filter(|code| code.starts_with(jis5))
+        .collect();
+    codes.sort();
+    codes
+        .into_iter()
+        .filter_map(|code| areas.values(&JmaAreaClass::Class20, code))
+        .collect()
+}
+
+/// Returns the prefecture code of the Office (or any area code with the prefecture prefix).
+///
+/// The offices of Hokkaido (`011000` to `017000`), 奄美地方 (`460040`) and Okinawa
+/// (`471000` to `474000`) share the prefecture code.
+pub fn prefecture_code(office: &str) -> Option<&'static str> {
+    let prefix = office.get(..2)?;
+    PREFECTURES
+        .iter()
+        .find(|(code, _, _)| *code == prefix)
+        .map(|(code, _, _)| *code)
+}
+
+/// Returns the Japanese name of the prefecture code.
+pub fn prefecture_name(prefecture: &str) -> Option<&'static str> {
+    PREFECTURES
+        .iter()
+        .find(|(code, _, _)| *code == prefecture)
+        .map(|(_, name, _)| *name)
+}
+
+/// Returns the Offices in the prefecture, ordered by code.
+pub fn offices_in_prefecture(areas: &Areas, prefecture: &str) -> Vec<Area> {
+    let mut codes: Vec<&String> = areas
+        .areas(&JmaAreaClass::Office)
+        .keys()
+        .filter(|code| prefecture_code(code) == Some(prefecture))
+        .collect();
+    codes.sort();
+    codes
+        .into_iter()
+        .filter_map(|code| areas.values(&JmaAreaClass::Office, code))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_digits() {
+        assert_eq!(check_digit("01100"), Some(2)); // 札幌市 011002
+        assert_eq!(check_digit("13101"), Some(6)); // 千代田区 131016
+        assert_eq!(check_digit("1010"), None);
+        assert_eq!(check_digit("1a201"), None);
+        assert_eq!(normalize_jis("102016"), Some("10201"));
+        assert_eq!(normalize_jis("102017"), None);
+        assert_eq!(normalize_jis("1020é"), None);
+        assert_eq!(normalize_jis("10201é"), None);
+        assert_eq!(jis_from_class20("0110000"), Some("011002".to_string()));
+        assert_eq!(jis_from_class20("011000"), None);
+    }
+
+    #[test]
+    fn class20() {
+        let areas = Areas::from_value(serde_json::json!({
+            "centers": {},
+            "offices": {
+                "011000": {"name": "宗谷地方", "enName": "Soya", "officeName": "稚内地方気象台", "parent": "010100", "children": []},
+                "016000": {"name": "石狩・空知・後志地方", "enName": "Ishikari Sorachi Shiribeshi", "officeName": "札幌管区気象台", "parent": "010100", "children": []},
+                "200000": {"name": "長野県", "enName": "Nagano", "officeName": "長野地方気象台", "parent": "010300", "children": []}
+            },
+            "class10s": {},
+            "class15s": {},
+            "class20s": {
+                "0110000": {"name": "札幌市", "enName": "Sapporo City", "kana": "さっぽろし", "parent": "016012"},
+                "2020202": {"name": "松本市梓川・波田地域", "enName": "Matsumoto City Azusagawa Hata", "parent": "200021"},
+                "2020201": {"name": "松本市松本地域", "enName": "Matsumoto City Matsumoto", "parent": "200021"}
+            }
+        }))
+        .unwrap();
+        let split = class20_from_jis(&areas, "202029");
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].code, "2020201");
+        // 中央区 (01101) is in 札幌市
+        let ward = class20_from_jis(&areas, "01101");
+        assert_eq!(ward[0].code, "0110000");
+        assert!(class20_from_jis(&areas, "202020").is_empty());
+
+        assert_eq!(prefecture_code("460040"), Some("46"));
+        assert_eq!(prefecture_code("480000"), None);
+        let offices = offices_in_prefecture(&areas, "01");
+        assert_eq!(offices.len(), 2);
+        assert_eq!(offices[0].code, "011000");
+    }
+}
@@ -1,4 +1,24 @@
 pub mod area;
+#[cfg(feature = "http")]
+pub mod client;
 pub mod forecast;
 pub mod forecast_area;
+pub mod forecast_text;
 pub mod amedas;
+pub mod verification;
+pub mod overview;
+pub mod search;
+pub mod jis;
+pub mod xref;
+pub mod schema;
+pub mod warning;
+pub mod output;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "icons")]
+pub mod icon;
+pub mod snapshot;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "geo")]
+pub mod geo;
@@ -0,0 +1,481 @@
+//! # Output Formats
+//!
+//! Render any `Serialize` results (observations, forecasts, areas, ...) as a table, JSON, NDJSON,
+//! CSV or YAML. The `jma` command uses this for `--format`.
+//!
+//! For the table and CSV, nested objects are flattened into dotted columns (e.g. `area.name`)
+//! and arrays of values are joined with commas.
+//!
+//! ## Example
+//! ```rust
+//! use jma::output::{render, OutputFormat};
+//! use jma::area::Areas;
+//!
+//! let areas = Areas::from_value(serde_json::json!({
+//!     "centers": {}, "offices": {}, "class10s": {}, "class15s": {},
+//!     "class20s": {"1020100": {"name": "前橋市", "enName": "Maebashi City", "parent": "100011"}}
+//! }))
+//! .unwrap();
+//! let found = areas.search("前橋");
+//! let csv = render(&found, OutputFormat::Csv).unwrap();
+//! assert_eq!(csv.lines().next().unwrap(),
+//!     "area.name,area.enName,area.kana,area.parent,area.officeName,area.children,class,code");
+//! print!("{}", render(&found, OutputFormat::Table).unwrap());
+//! ```
+
+use serde::Serialize;
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// Aligned columns for humans.
+    Table,
+    /// A pretty-printed JSON array.
+    Json,
+    /// A JSON object per line.
+    Ndjson,
+    Csv,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// convert str to OutputFormat.
+    pub fn to_output_format(format: &str) -> Option<OutputFormat> {
+        match format.to_lowercase().as_str() {
+            "table" => Some(OutputFormat::Table),
+            "json" => Some(OutputFormat::Json),
+            "ndjson" | "jsonl" => Some(OutputFormat::Ndjson),
+            "csv" => Some(OutputFormat::Csv),
+            "yaml" | "yml" => Some(OutputFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// A JSON value keeping the object keys in the order they were serialized.
+///
+/// `serde_json::Value` sorts the keys, so the records are read back from the JSON text.
+enum Node {
+    /// Null, a boolean, a number or a string.
+    Scalar(Value),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Node, D::Error> {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::Null))
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::Bool(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::String(v.to_string())))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Node::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Node::Object(entries))
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::Scalar(value) => value.serialize(serializer),
+            Node::Array(items) => items.serialize(serializer),
+            Node::Object(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Render the records in the format.
+pub fn render<T: Serialize>(
+    records: &[T],
+    format: OutputFormat,
+) -> Result<String, serde_json::Error> {
+    let output = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(records)? + "\n",
+        OutputFormat::Ndjson => {
+            let mut output = String::new();
+            for record in records {
+                output.push_str(&serde_json::to_string(record)?);
+                output.push('\n');
+            }
+            output
+        }
+        _ => {
+            let nodes: Vec<Node> = serde_json::from_str(&serde_json::to_string(records)?)?;
+            match format {
+                OutputFormat::Csv => render_csv(&nodes),
+                OutputFormat::Yaml => render_yaml(nodes),
+                _ => render_table(&nodes),
+            }
+        }
+    };
+    Ok(output)
+}
+
+/// Flatten the record into (column, cell) pairs.
+fn flatten(node: &Node, column: &str, cells: &mut Vec<(String, String)>) {
+    match node {
+        Node::Object(entries) if !entries.is_empty() => {
+            for (key, v) in entries {
+                let column = if column.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", column, key)
+                };
+                flatten(v, &column, cells);
+            }
+        }
+        _ => {
+            let column = if column.is_empty() { "value" } else { column };
+            cells.push((column.to_string(), cell(node)));
+        }
+    }
+}
+
+fn cell(node: &Node) -> String {
+    match node {
+        Node::Scalar(Value::Null) => String::new(),
+        Node::Scalar(Value::String(s)) => s.clone(),
+        Node::Scalar(value) => value.to_string(),
+        Node::Array(items) if items.iter().all(|v| matches!(v, Node::Scalar(_))) => {
+            items.iter().map(cell).collect::<Vec<String>>().join(",")
+        }
+        _ => serde_json::to_string(node).unwrap_or_default(),
+    }
+}
+
+/// The columns in the order of appearance and the cells of each record.
+fn columns_and_rows(nodes: &[Node]) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut columns: Vec<String> = Vec::new();
+    let records: Vec<Vec<(String, String)>> = nodes
+        .iter()
+        .map(|node| {
+            let mut cells = Vec::new();
+            flatten(node, "", &mut cells);
+            for (column, _) in &cells {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+            cells
+        })
+        .collect();
+    let rows = records
+        .into_iter()
+        .map(|cells| {
+            columns
+                .iter()
+                .map(|column| {
+                    cells
+                        .iter()
+                        .find(|(c, _)| c == column)
+                        .map(|(_, v)| v.clone())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+    (columns, rows)
+}
+
+/// Display width, counting East Asian wide characters as 2.
+fn width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F
+            | 0x2E80..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F300..=0x1FAFF
+            | 0x20000..=0x3FFFD => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn render_table(nodes: &[Node]) -> String {
+    let (columns, rows) = columns_and_rows(nodes);
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| width(&row[i]))
+                .chain([width(column)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &[String]| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{}{}", cell, " ".repeat(w - width(cell))))
+            .collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let mut output = line(&columns);
+    let rules: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    output.push_str(&line(&rules));
+    for row in &rows {
+        output.push_str(&line(row));
+    }
+    output
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_csv(nodes: &[Node]) -> String {
+    let (columns, rows) = columns_and_rows(nodes);
+    let mut output = String::new();
+    for row in std::iter::once(&columns).chain(&rows) {
+        let fields: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// A YAML scalar, double-quoted (as JSON) if it would be read as another type or syntax.
+fn yaml_string(s: &str) -> String {
+    let plain = !s.is_empty()
+        && !s.starts_with(|c: char| {
+            c.is_ascii_digit() || c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`.+~".contains(c)
+        })
+        && !s.ends_with(|c: char| c.is_whitespace() || c == ':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.contains(|c: char| c.is_control())
+        && !matches!(
+            s.to_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "y" | "n"
+        );
+    if plain {
+        s.to_string()
+    } else {
+        Value::String(s.to_string()).to_string()
+    }
+}
+
+fn yaml_scalar(node: &Node) -> String {
+    match node {
+        Node::Scalar(Value::Null) => "null".to_string(),
+        Node::Scalar(Value::String(s)) => yaml_string(s),
+        Node::Scalar(value) => value.to_string(),
+        Node::Array(_) => "[]".to_string(),
+        Node::Object(_) => "{}".to_string(),
+    }
+}
+
+fn is_block(node: &Node) -> bool {
+    match node {
+        Node::Array(items) => !items.is_empty(),
+        Node::Object(entries) => !entries.is_empty(),
+        Node::Scalar(_) => false,
+    }
+}
+
+/// Write the block collection; the first line is already indented (after `- `) if `inline`.
+fn write_yaml(node: &Node, indent: usize, inline: bool, output: &mut String) {
+    let mut first = true;
+    let mut start = |output: &mut String| {
+        if !(inline && first) {
+            output.push_str(&" ".repeat(indent));
+        }
+        first = false;
+    };
+    match node {
+        Node::Array(items) => {
+            for item in items {
+                start(output);
+                output.push_str("- ");
+                if is_block(item) {
+                    write_yaml(item, indent + 2, true, output);
+                } else {
+                    output.push_str(&yaml_scalar(item));
+                    output.push('\n');
+                }
+            }
+        }
+        Node::Object(entries) => {
+            for (key, v) in entries {
+                start(output);
+                output.push_str(&yaml_string(key));
+                output.push(':');
+                if is_block(v) {
+                    output.push('\n');
+                    write_yaml(v, indent + 2, false, output);
+                } else {
+                    output.push(' ');
+                    output.push_str(&yaml_scalar(v));
+                    output.push('\n');
+                }
+            }
+        }
+        Node::Scalar(_) => {
+            start(output);
+            output.push_str(&yaml_scalar(node));
+            output.push('\n');
+        }
+    }
+}
+
+fn render_yaml(nodes: Vec<Node>) -> String {
+    let mut output = String::new();
+    if nodes.is_empty() {
+        output.push_str("[]\n");
+    }
+    write_yaml(&Node::Array(nodes), 0, false, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast::{Temps, TempsArea};
+    use crate::xref::Class10Entry;
+
+    fn temps() -> Vec<Temps> {
+        vec![
+            Temps {
+                area: TempsArea {
+                    code: "31312".to_string(),
+                    name: "青森".to_string(),
+                },
+                temps: vec!["3".to_string(), "9".to_string()],
+            },
+            Temps {
+                area: TempsArea {
+                    code: "31602".to_string(),
+                    name: "むつ, 下北".to_string(),
+                },
+                temps: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(
+            render(&temps(), OutputFormat::Table).unwrap(),
+            "area.code  area.name   temps\n\
+             ---------  ----------  -----\n\
+             31312      青森        3,9\n\
+             31602      むつ, 下北\n"
+        );
+        assert_eq!(
+            render(&temps(), OutputFormat::Csv).unwrap(),
+            "area.code,area.name,temps\n31312,青森,\"3,9\"\n31602,\"むつ, 下北\",\n"
+        );
+        assert_eq!(
+            render(&temps(), OutputFormat::Ndjson)
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap(),
+            r#"{"area":{"code":"31312","name":"青森"},"temps":["3","9"]}"#
+        );
+        assert_eq!(
+            render(&temps(), OutputFormat::Yaml).unwrap(),
+            "- area:\n    code: \"31312\"\n    name: 青森\n  temps:\n    - \"3\"\n    - \"9\"\n\
+             - area:\n    code: \"31602\"\n    name: むつ, 下北\n  temps: []\n"
+        );
+        let json: Value =
+            serde_json::from_str(&render(&temps(), OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[1]["area"]["name"], "むつ, 下北");
+        assert_eq!(render::<Temps>(&[], OutputFormat::Yaml).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn field_order() {
+        let entry = Class10Entry {
+            office: "200000".to_string(),
+            class10: "200010".to_string(),
+            class20: "2020100".to_string(),
+            amedas: vec!["48156".to_string()],
+            temperature_point: None,
+        };
+        assert_eq!(
+            render(&[entry], OutputFormat::Csv).unwrap(),
+            "office,class10,class20,amedas,temperature_point\n200000,200010,2020100,48156,\n"
+        );
+    }
+
+    #[test]
+    fn yaml_strings() {
+        assert_eq!(yaml_string("晴れ"), "晴れ");
+        assert_eq!(yaml_string("020010"), "\"020010\"");
+        assert_eq!(yaml_string("no"), "\"no\"");
+        assert_eq!(yaml_string(""), "\"\"");
+        assert_eq!(yaml_string("a: b"), "\"a: b\"");
+        assert_eq!(yaml_string("a:"), "\"a:\"");
+        assert_eq!(yaml_string("-1"), "\"-1\"");
+        assert_eq!(
+            OutputFormat::to_output_format("JSONL"),
+            Some(OutputFormat::Ndjson)
+        );
+        assert_eq!(OutputFormat::to_output_format("xml"), None);
+    }
+}
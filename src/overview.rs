@@ -0,0 +1,237 @@
+//! # Fetch Weather Overview from JMA
+//!
+//! ## API and JSON
+//! <https://www.jma.go.jp/bosai/forecast/data/overview_forecast/>{office_code}.json
+//!
+//! Example of 020000.json
+//! ```json
+//! {
+//!   "publishingOffice": "青森地方気象台",
+//!   "reportDatetime": "2025-03-28T16:37:00+09:00",
+//!   "targetArea": "青森県",
+//!   "headlineText": "",
+//!   "text": "　青森県は、冬型の気圧配置となっています。\n\n　２９日は、冬型の気圧配置が続くでしょう。"
+//! }
+//! ```
+//!
+//! <https://www.jma.go.jp/bosai/forecast/data/overview_week/>{office_code}.json
+//!
+//! Example of 020000.json
+//! ```json
+//! {
+//!   "publishingOffice": "青森地方気象台",
+//!   "reportDatetime": "2025-03-28T16:37:00+09:00",
+//!   "headTitle": "東北地方週間天気予報",
+//!   "text": "【東北地方】\n　東北地方は、期間のはじめは気圧の谷や寒気の影響で曇りや雨または雪の所があるでしょう。"
+//! }
+//! ```
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::overview::OverviewForecast;
+//!
+//! # #[cfg(feature = "http")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let overview = OverviewForecast::new("020000").await.unwrap();
+//!     let (headline, body) = overview.split_text();
+//!     println!("{} {}", overview.publishing_office, overview.report_datetime);
+//!     if let Some(headline) = headline {
+//!         println!("{}", headline);
+//!     }
+//!     for paragraph in body {
+//!         println!("{}", paragraph);
+//!     }
+//! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
+//! ```
+
+#[cfg(feature = "http")]
+use crate::client::{ClientError, Resource, default_client};
+#[cfg(feature = "http")]
+use crate::forecast::office_for_url;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "http")]
+const OVERVIEW_FORECAST_URL: &str = "https://www.jma.go.jp/bosai/forecast/data/overview_forecast";
+#[cfg(feature = "http")]
+const OVERVIEW_WEEK_URL: &str = "https://www.jma.go.jp/bosai/forecast/data/overview_week";
+
+/// Weather overview (天気概況) of an office region.
+///
+/// `overview_forecast/{office}.json`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverviewForecast {
+    pub publishing_office: String,
+    pub report_datetime: String,
+    pub target_area: String,
+    #[serde(default)]
+    pub headline_text: String,
+    pub text: String,
+}
+
+impl OverviewForecast {
+    /// Fetch the weather overview of the office region.
+    #[cfg(feature = "http")]
+    pub async fn new(office: &str) -> Result<OverviewForecast, ClientError> {
+        let url = format!("{}/{}.json", OVERVIEW_FORECAST_URL, office_for_url(office));
+        default_client().get_json(&url, Resource::Forecast).await
+    }
+
+    /// Parse overview_forecast/{office}.json.
+    pub fn from_json_str(json: &str) -> Result<OverviewForecast, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse overview_forecast/{office}.json from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<OverviewForecast, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed overview_forecast/{office}.json.
+    pub fn from_value(json: serde_json::Value) -> Result<OverviewForecast, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+
+    /// Returns the headline and the body paragraphs.
+    ///
+    /// The headline is `headlineText`, or `None` if it is empty.
+    pub fn split_text(&self) -> (Option<String>, Vec<String>) {
+        let headline = self.headline_text.trim();
+        let headline = if headline.is_empty() {
+            None
+        } else {
+            Some(headline.to_string())
+        };
+        (headline, paragraphs(&self.text))
+    }
+}
+
+/// Weekly weather overview of an office region.
+///
+/// `overview_week/{office}.json`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverviewWeek {
+    pub publishing_office: String,
+    pub report_datetime: String,
+    pub head_title: String,
+    pub text: String,
+}
+
+impl OverviewWeek {
+    /// Fetch the weekly weather overview of the office region.
+    #[cfg(feature = "http")]
+    pub async fn new(office: &str) -> Result<OverviewWeek, ClientError> {
+        let url = format!("{}/{}.json", OVERVIEW_WEEK_URL, office_for_url(office));
+        default_client().get_json(&url, Resource::Forecast).await
+    }
+
+    /// Parse overview_week/{office}.json.
+    pub fn from_json_str(json: &str) -> Result<OverviewWeek, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse overview_week/{office}.json from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<OverviewWeek, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed overview_week/{office}.json.
+    pub fn from_value(json: serde_json::Value) -> Result<OverviewWeek, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+
+    /// Returns the headline and the body paragraphs.
+    ///
+    /// The headline is the first line if it is a bracketed title such as "【東北地方】".
+    pub fn split_text(&self) -> (Option<String>, Vec<String>) {
+        let mut paragraphs = paragraphs(&self.text);
+        let headline = match paragraphs.first() {
+            Some(first) if first.starts_with('【') => {
+                let mut lines = first.splitn(2, '\n');
+                let headline = lines.next().unwrap_or_default().to_string();
+                match lines.next().map(trim_paragraph) {
+                    Some(rest) if !rest.is_empty() => paragraphs[0] = rest,
+                    _ => {
+                        paragraphs.remove(0);
+                    }
+                }
+                Some(headline)
+            }
+            _ => None,
+        };
+        (headline, paragraphs)
+    }
+}
+
+fn trim_paragraph(paragraph: &str) -> String {
+    paragraph
+        .trim_matches(|c: char| c.is_whitespace() || c == '\u{3000}')
+        .to_string()
+}
+
+/// Split the overview text into paragraphs separated by blank lines.
+///
+/// The indentation (full-width spaces) and trailing spaces are removed.
+pub fn paragraphs(text: &str) -> Vec<String> {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(trim_paragraph)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_overview_forecast() {
+        let overview: OverviewForecast = serde_json::from_str(
+            r#"{
+              "publishingOffice": "青森地方気象台",
+              "reportDatetime": "2025-03-28T16:37:00+09:00",
+              "targetArea": "青森県",
+              "headlineText": "",
+              "text": "　青森県は、冬型の気圧配置となっています。\n\n　２９日は、冬型の気圧配置が続くでしょう。\n\n"
+            }"#,
+        )
+        .unwrap();
+        let (headline, body) = overview.split_text();
+        assert_eq!(headline, None);
+        assert_eq!(body.len(), 2);
+        assert_eq!(body[1], "２９日は、冬型の気圧配置が続くでしょう。");
+    }
+
+    #[test]
+    fn split_overview_week() {
+        let overview: OverviewWeek = serde_json::from_str(
+            r#"{
+              "publishingOffice": "青森地方気象台",
+              "reportDatetime": "2025-03-28T16:37:00+09:00",
+              "headTitle": "東北地方週間天気予報",
+              "text": "【東北地方】\n　東北地方は、期間のはじめは曇りでしょう。\n\n　最高気温は平年並です。"
+            }"#,
+        )
+        .unwrap();
+        let (headline, body) = overview.split_text();
+        assert_eq!(headline, Some("【東北地方】".to_string()));
+        assert_eq!(
+            body,
+            vec![
+                "東北地方は、期間のはじめは曇りでしょう。",
+                "最高気温は平年並です。"
+            ]
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn get_overview_forecast() {
+        let overview = OverviewForecast::new("020000").await.unwrap();
+        assert_eq!(overview.publishing_office, "青森地方気象台");
+    }
+}
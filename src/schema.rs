@@ -0,0 +1,530 @@
+//! # Schema Drift Detection
+//!
+//! JMA changes the JSON without notice. The payloads are checked against the fields this crate
+//! expects, and the differences are reported as [`SchemaIssue`]s instead of being ignored by
+//! serde or failing later.
+//!
+//! | Payload | File |
+//! | :--- | :--- |
+//! | [`Payload::Area`] | area.json |
+//! | [`Payload::ForecastArea`] | forecast_area.json |
+//! | [`Payload::AmedasTable`] | amedastable.json |
+//! | [`Payload::AmedasPoint`] | {date}_{hour}.json in `point/{amedas}/`, e.g. 20251118_09.json |
+//! | [`Payload::Forecast`] | {office}.json, e.g. 020000.json |
+//!
+//! Paths are written as in the module documents, e.g. `[0].timeSeries[2].areas[0].temps`.
+//! Run `cargo run --example validate -- <dir>` to check a directory of saved samples.
+//!
+//! ## Example
+//! ```rust
+//! use jma::area::Areas;
+//! use jma::schema::{parse_with_report, Payload, SchemaIssueKind};
+//!
+//! let json = r#"{"centers": {}, "offices": {}, "class10s": {}, "class15s": {},
+//!     "class20s": {"1020100": {"name": "前橋市", "enName": "Maebashi City",
+//!                              "parent": "100011", "population": 330000}}}"#;
+//! let (areas, issues) = parse_with_report::<Areas>(Payload::Area, json).unwrap();
+//! assert_eq!(areas.areas(&jma::area::JmaAreaClass::Class20).len(), 1);
+//! assert_eq!(issues[0].path, "class20s.1020100.population");
+//! assert_eq!(issues[0].kind, SchemaIssueKind::UnknownField);
+//! ```
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The kinds of the JMA payloads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Payload {
+    Area,
+    ForecastArea,
+    AmedasTable,
+    AmedasPoint,
+    Forecast,
+}
+
+impl Payload {
+    /// Guess the payload from the file name as published by JMA.
+    pub fn from_file_name(name: &str) -> Option<Payload> {
+        let stem = name.strip_suffix(".json")?;
+        let digits = |s: &str, n: usize| s.len() == n && s.chars().all(|c| c.is_ascii_digit());
+        match stem {
+            "area" => Some(Payload::Area),
+            "forecast_area" => Some(Payload::ForecastArea),
+            "amedastable" => Some(Payload::AmedasTable),
+            _ if digits(stem, 6) => Some(Payload::Forecast),
+            _ => match stem.split_once('_') {
+                Some((date, hour)) if digits(date, 8) && digits(hour, 2) => {
+                    Some(Payload::AmedasPoint)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn schema(&self) -> &'static Schema {
+        match self {
+            Payload::Area => &AREA,
+            Payload::ForecastArea => &FORECAST_AREA,
+            Payload::AmedasTable => &AMEDAS_TABLE,
+            Payload::AmedasPoint => &AMEDAS_POINT,
+            Payload::Forecast => &FORECAST,
+        }
+    }
+}
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Payload::Area => "area.json",
+            Payload::ForecastArea => "forecast_area.json",
+            Payload::AmedasTable => "amedastable.json",
+            Payload::AmedasPoint => "{date}_{hour}.json",
+            Payload::Forecast => "{office}.json",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub enum SchemaIssueKind {
+    /// A field this crate does not know.
+    UnknownField,
+    /// A required field is missing.
+    MissingField,
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// An array of a fixed length (e.g. `[value, quality]`) has another length.
+    UnexpectedLength { expected: usize, found: usize },
+}
+
+/// A difference between a payload and the expected schema.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SchemaIssue {
+    pub path: String,
+    pub kind: SchemaIssueKind,
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            SchemaIssueKind::UnknownField => write!(f, "{}: unknown field", self.path),
+            SchemaIssueKind::MissingField => write!(f, "{}: missing field", self.path),
+            SchemaIssueKind::UnexpectedType { expected, found } => {
+                write!(f, "{}: expected {}, found {}", self.path, expected, found)
+            }
+            SchemaIssueKind::UnexpectedLength { expected, found } => write!(
+                f,
+                "{}: expected {} elements, found {}",
+                self.path, expected, found
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Schema {
+    Any,
+    String,
+    Number,
+    /// A number or null, e.g. a missing observation.
+    NullableNumber,
+    Array(&'static Schema),
+    Tuple(&'static [Schema]),
+    /// An object keyed by codes.
+    Map(&'static Schema),
+    Object(&'static [Field]),
+}
+
+#[derive(Debug)]
+struct Field {
+    name: &'static str,
+    schema: Schema,
+    required: bool,
+}
+
+const fn required(name: &'static str, schema: Schema) -> Field {
+    Field {
+        name,
+        schema,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, schema: Schema) -> Field {
+    Field {
+        name,
+        schema,
+        required: false,
+    }
+}
+
+const STRINGS: Schema = Schema::Array(&Schema::String);
+
+const RAW_AREA: Schema = Schema::Object(&[
+    required("name", Schema::String),
+    required("enName", Schema::String),
+    optional("kana", Schema::String),
+    optional("parent", Schema::String),
+    optional("officeName", Schema::String),
+    optional("children", STRINGS),
+]);
+
+const AREA: Schema = Schema::Object(&[
+    required("centers", Schema::Map(&RAW_AREA)),
+    required("offices", Schema::Map(&RAW_AREA)),
+    required("class10s", Schema::Map(&RAW_AREA)),
+    required("class15s", Schema::Map(&RAW_AREA)),
+    required("class20s", Schema::Map(&RAW_AREA)),
+]);
+
+const FORECAST_AREA: Schema = Schema::Map(&Schema::Array(&Schema::Object(&[
+    required("class10", Schema::String),
+    required("amedas", STRINGS),
+    required("class20", Schema::String),
+])));
+
+const DEGREES: Schema = Schema::Tuple(&[Schema::Number, Schema::Number]);
+
+const AMEDAS_TABLE: Schema = Schema::Map(&Schema::Object(&[
+    required("type", Schema::String),
+    required("elems", Schema::String),
+    required("lat", DEGREES),
+    required("lon", DEGREES),
+    required("alt", Schema::Number),
+    required("kjName", Schema::String),
+    required("knName", Schema::String),
+    required("enName", Schema::String),
+]));
+
+/// `[value, quality]`
+const ELEMENT: Schema = Schema::Tuple(&[Schema::NullableNumber, Schema::NullableNumber]);
+
+/// The elements parsed by [`crate::amedas::AmedasRawData`] are required.
+const AMEDAS_POINT: Schema = Schema::Map(&Schema::Object(&[
+    optional("prefNumber", Schema::Number),
+    optional("observationNumber", Schema::Number),
+    optional("pressure", ELEMENT),
+    optional("normalPressure", ELEMENT),
+    required("temp", ELEMENT),
+    required("humidity", ELEMENT),
+    optional("visibility", ELEMENT),
+    optional("weather", ELEMENT),
+    optional("snow", ELEMENT),
+    optional("snow1h", ELEMENT),
+    optional("snow6h", ELEMENT),
+    optional("snow12h", ELEMENT),
+    optional("snow24h", ELEMENT),
+    optional("sun10m", ELEMENT),
+    optional("sun1h", ELEMENT),
+    required("precipitation10m", ELEMENT),
+    required("precipitation1h", ELEMENT),
+    optional("precipitation3h", ELEMENT),
+    optional("precipitation24h", ELEMENT),
+    required("windDirection", ELEMENT),
+    required("wind", ELEMENT),
+    optional("maxTempTime", Schema::Any),
+    optional("maxTemp", ELEMENT),
+    optional("minTempTime", Schema::Any),
+    optional("minTemp", ELEMENT),
+    optional("gustTime", Schema::Any),
+    optional("gustDirection", ELEMENT),
+    optional("gust", ELEMENT),
+]));
+
+const AREA_CODE: Schema = Schema::Object(&[
+    required("name", Schema::String),
+    required("code", Schema::String),
+]);
+
+/// The areas of all the time series: each series has some of the arrays.
+const FORECAST_SERIES_AREA: Schema = Schema::Object(&[
+    required("area", AREA_CODE),
+    optional("weatherCodes", STRINGS),
+    optional("weathers", STRINGS),
+    optional("winds", STRINGS),
+    optional("waves", STRINGS),
+    optional("pops", STRINGS),
+    optional("temps", STRINGS),
+    optional("reliabilities", STRINGS),
+    optional("tempsMin", STRINGS),
+    optional("tempsMinUpper", STRINGS),
+    optional("tempsMinLower", STRINGS),
+    optional("tempsMax", STRINGS),
+    optional("tempsMaxUpper", STRINGS),
+    optional("tempsMaxLower", STRINGS),
+]);
+
+const AVERAGE: Schema = Schema::Object(&[required(
+    "areas",
+    Schema::Array(&Schema::Object(&[
+        required("area", AREA_CODE),
+        required("min", Schema::String),
+        required("max", Schema::String),
+    ])),
+)]);
+
+const FORECAST: Schema = Schema::Array(&Schema::Object(&[
+    required("publishingOffice", Schema::String),
+    required("reportDatetime", Schema::String),
+    required(
+        "timeSeries",
+        Schema::Array(&Schema::Object(&[
+            required("timeDefines", STRINGS),
+            required("areas", Schema::Array(&FORECAST_SERIES_AREA)),
+        ])),
+    ),
+    optional("tempAverage", AVERAGE),
+    optional("precipAverage", AVERAGE),
+]));
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn check(schema: &Schema, value: &Value, path: &str, issues: &mut Vec<SchemaIssue>) {
+    let expected = match (schema, value) {
+        (Schema::Any, _)
+        | (Schema::String, Value::String(_))
+        | (Schema::Number, Value::Number(_))
+        | (Schema::NullableNumber, Value::Number(_) | Value::Null) => return,
+        (Schema::Array(item), Value::Array(values)) => {
+            for (i, v) in values.iter().enumerate() {
+                check(item, v, &format!("{}[{}]", path, i), issues);
+            }
+            return;
+        }
+        (Schema::Tuple(items), Value::Array(values)) => {
+            if items.len() != values.len() {
+                issues.push(SchemaIssue {
+                    path: path.to_string(),
+                    kind: SchemaIssueKind::UnexpectedLength {
+                        expected: items.len(),
+                        found: values.len(),
+                    },
+                });
+            }
+            for (i, (item, v)) in items.iter().zip(values).enumerate() {
+                check(item, v, &format!("{}[{}]", path, i), issues);
+            }
+            return;
+        }
+        (Schema::Map(item), Value::Object(map)) => {
+            for (key, v) in map {
+                check(item, v, &join(path, key), issues);
+            }
+            return;
+        }
+        (Schema::Object(fields), Value::Object(map)) => {
+            for field in fields.iter() {
+                match map.get(field.name) {
+                    Some(Value::Null) if !field.required => {}
+                    Some(v) => check(&field.schema, v, &join(path, field.name), issues),
+                    None if field.required => issues.push(SchemaIssue {
+                        path: join(path, field.name),
+                        kind: SchemaIssueKind::MissingField,
+                    }),
+                    None => {}
+                }
+            }
+            for key in map.keys() {
+                if !fields.iter().any(|f| f.name == key) {
+                    issues.push(SchemaIssue {
+                        path: join(path, key),
+                        kind: SchemaIssueKind::UnknownField,
+                    });
+                }
+            }
+            return;
+        }
+        (Schema::String, _) => "string",
+        (Schema::Number, _) => "number",
+        (Schema::NullableNumber, _) => "number or null",
+        (Schema::Array(_) | Schema::Tuple(_), _) => "array",
+        (Schema::Map(_) | Schema::Object(_), _) => "object",
+    };
+    issues.push(SchemaIssue {
+        path: path.to_string(),
+        kind: SchemaIssueKind::UnexpectedType {
+            expected,
+            found: type_name(value),
+        },
+    });
+}
+
+/// Check the payload against the expected schema.
+pub fn validate(payload: Payload, json: &Value) -> Vec<SchemaIssue> {
+    let mut issues = Vec::new();
+    check(payload.schema(), json, "", &mut issues);
+    issues
+}
+
+/// Parse the payload and report the schema issues, which are also logged as warnings.
+///
+/// Issues the type tolerates (e.g. unknown fields) are returned with the parsed value;
+/// the others come back as the serde error.
+pub fn parse_with_report<T: DeserializeOwned>(
+    payload: Payload,
+    json: &str,
+) -> Result<(T, Vec<SchemaIssue>), serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+    let issues = validate(payload, &value);
+    for issue in &issues {
+        log::warn!("{}: {}", payload, issue);
+    }
+    Ok((serde_json::from_value(value)?, issues))
+}
+
+/// The result of a file checked by [`validate_dir`].
+#[derive(Debug)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub payload: Payload,
+    pub issues: Vec<SchemaIssue>,
+    /// The file is not valid JSON (or not UTF-8).
+    pub error: Option<String>,
+}
+
+impl FileReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty() && self.error.is_none()
+    }
+}
+
+/// Check the JMA files in the directory, ordered by the file name.
+///
+/// Files whose kind is not known from the name (see [`Payload::from_file_name`]) are skipped.
+pub fn validate_dir<P: AsRef<Path>>(dir: P) -> std::io::Result<Vec<FileReport>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    paths.sort();
+
+    let mut reports = Vec::new();
+    for path in paths {
+        let payload = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => match Payload::from_file_name(name) {
+                Some(payload) => payload,
+                None => continue,
+            },
+            None => continue,
+        };
+        let bytes = std::fs::read(&path)?;
+        let (issues, error) = match serde_json::from_slice::<Value>(&bytes) {
+            Ok(json) => (validate(payload, &json), None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        reports.push(FileReport {
+            path,
+            payload,
+            issues,
+            error,
+        });
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names() {
+        assert_eq!(Payload::from_file_name("area.json"), Some(Payload::Area));
+        assert_eq!(
+            Payload::from_file_name("20251118_09.json"),
+            Some(Payload::AmedasPoint)
+        );
+        assert_eq!(
+            Payload::from_file_name("020000.json"),
+            Some(Payload::Forecast)
+        );
+        assert_eq!(Payload::from_file_name("SNAPSHOT_DATE"), None);
+        assert_eq!(Payload::from_file_name("20251118_9.json"), None);
+        assert_eq!(Payload::from_file_name("14163_09.json"), None);
+    }
+
+    #[test]
+    fn broken_files() {
+        let dir = std::env::temp_dir().join(format!("jma-schema-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("020000.json"), b"[\"\xff\"]").unwrap();
+        std::fs::write(dir.join("area.json"), "{").unwrap();
+        let reports = validate_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.error.is_some()));
+    }
+
+    #[test]
+    fn sample_tables() {
+        let reports = validate_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata")).unwrap();
+        assert_eq!(reports.len(), 3);
+        for report in reports {
+            assert!(report.is_ok(), "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn drift() {
+        let json = serde_json::json!([{
+            "publishingOffice": "青森地方気象台",
+            "reportDatetime": "2025-03-28T17:00:00+09:00",
+            "timeSeries": [{
+                "timeDafines": ["2025-03-28T17:00:00+09:00"],
+                "areas": [{"area": {"name": "津軽", "code": 20010}, "pops": ["30"]}]
+            }]
+        }]);
+        let issues = validate(Payload::Forecast, &json);
+        let messages: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "[0].timeSeries[0].timeDefines: missing field",
+                "[0].timeSeries[0].areas[0].area.code: expected string, found number",
+                "[0].timeSeries[0].timeDafines: unknown field",
+            ]
+        );
+
+        let json = serde_json::json!({
+            "20261018120000": {
+                "temp": [12.3, 0],
+                "humidity": [80],
+                "precipitation10m": [0.0, 0],
+                "precipitation1h": [0.0, 0],
+                "windDirection": [null, 0],
+                "wind": [1.2, 0]
+            }
+        });
+        let issues = validate(Payload::AmedasPoint, &json);
+        assert_eq!(
+            issues,
+            [SchemaIssue {
+                path: "20261018120000.humidity".to_string(),
+                kind: SchemaIssueKind::UnexpectedLength {
+                    expected: 2,
+                    found: 1
+                },
+            }]
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! # Search Areas
+//!
+//! Search index of area.json used by [`Areas::search`](crate::area::Areas::search).
+//!
+//! The keyword is matched against the code, the name (kanji), `kana` (hiragana or katakana),
+//! `enName` and the Hepburn romaji of `kana`. A kana keyword is also matched as romaji, so
+//! "akita", "あきた", "アキタ" and "秋田" find 秋田県 first.
+//!
+//! Each area appears once with its best match, ranked exact > prefix > substring > fuzzy,
+//! then by class (Center first) and by code.
+//!
+//! ## Example
+//! ```rust
+//! use jma::search::{to_romaji, MatchKind};
+//!
+//! assert_eq!(to_romaji("しぶかわし"), "shibukawashi");
+//! assert_eq!(to_romaji("ハッチョウ"), "hatchou");
+//! assert!(MatchKind::Exact < MatchKind::Fuzzy);
+//! ```
+
+use crate::area::{Area, Areas, JmaAreaClass};
+use crate::forecast_text::normalize;
+use serde::Serialize;
+
+/// How the keyword matched, from the best.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Substring,
+    /// Within a small edit distance of the key or of its beginning.
+    Fuzzy,
+}
+
+/// An area found by the search.
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchHit {
+    pub area: Area,
+    pub kind: MatchKind,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    area: Area,
+    name: String,
+    kana: Option<String>,
+    english: String,
+    romaji: Option<String>,
+}
+
+/// Search index of the areas.
+#[derive(Debug, Default, Clone)]
+pub struct SearchIndex {
+    entries: Vec<Entry>,
+}
+
+const CLASSES: [JmaAreaClass; 5] = [
+    JmaAreaClass::Center,
+    JmaAreaClass::Office,
+    JmaAreaClass::Class10,
+    JmaAreaClass::Class15,
+    JmaAreaClass::Class20,
+];
+
+fn class_rank(class: &JmaAreaClass) -> usize {
+    CLASSES.iter().position(|c| c == class).unwrap()
+}
+
+/// Convert katakana to hiragana.
+pub fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+fn kana_romaji(c: char) -> Option<&'static str> {
+    let romaji = match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' => "wa",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    };
+    Some(romaji)
+}
+
+/// Convert hiragana or katakana to Hepburn romaji. Other characters are kept.
+pub fn to_romaji(kana: &str) -> String {
+    let chars: Vec<char> = to_hiragana(kana).chars().collect();
+    let mut romaji = String::new();
+    let mut double = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == 'っ' {
+            double = true;
+            i += 1;
+            continue;
+        }
+        let mut syllable = match kana_romaji(c) {
+            Some(r) => r.to_string(),
+            None => {
+                if c != 'ー' {
+                    romaji.push(c);
+                }
+                i += 1;
+                continue;
+            }
+        };
+        // Digraphs such as きゃ (kya) and しょ (sho)
+        if let Some(&next) = chars.get(i + 1)
+            && matches!(next, 'ゃ' | 'ゅ' | 'ょ')
+            && syllable.ends_with('i')
+            && syllable.len() > 1
+        {
+            let stem = &syllable[..syllable.len() - 1];
+            let vowel = &kana_romaji(next).unwrap()[1..];
+            syllable = if stem.ends_with("sh") || stem.ends_with("ch") || stem == "j" {
+                format!("{}{}", stem, vowel)
+            } else {
+                format!("{}y{}", stem, vowel)
+            };
+            i += 1;
+        }
+        if double {
+            romaji.push(if syllable.starts_with("ch") {
+                't'
+            } else {
+                syllable.chars().next().unwrap()
+            });
+            double = false;
+        }
+        romaji.push_str(&syllable);
+        i += 1;
+    }
+    romaji
+}
+
+/// Lowercase alphanumerics with the long vowels folded ("Tōkyō", "toukyou" and "Tokyo" are "tokyo").
+fn fold_romaji(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter_map(|c| match c {
+            'ā' | 'â' => Some('a'),
+            'ī' | 'î' => Some('i'),
+            'ū' | 'û' => Some('u'),
+            'ē' | 'ê' => Some('e'),
+            'ō' | 'ô' => Some('o'),
+            c if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect();
+    text.replace("ou", "o")
+        .replace("oo", "o")
+        .replace("uu", "u")
+}
+
+fn is_kana(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| matches!(c, '\u{3041}'..='\u{3096}' | 'ー'))
+}
+
+/// Edit distance counting a transposition of adjacent characters as one edit.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+fn match_kind(keyword: &str, key: &str) -> Option<MatchKind> {
+    if keyword.is_empty() || key.is_empty() {
+        return None;
+    }
+    if key == keyword {
+        return Some(MatchKind::Exact);
+    }
+    if key.starts_with(keyword) {
+        return Some(MatchKind::Prefix);
+    }
+    if key.contains(keyword) {
+        return Some(MatchKind::Substring);
+    }
+    let keyword: Vec<char> = keyword.chars().collect();
+    if keyword.len() < 3 {
+        return None;
+    }
+    let threshold = if keyword.len() < 8 { 1 } else { 2 };
+    let key: Vec<char> = key.chars().collect();
+    let head = &key[..key.len().min(keyword.len())];
+    if edit_distance(&keyword, &key).min(edit_distance(&keyword, head)) <= threshold {
+        Some(MatchKind::Fuzzy)
+    } else {
+        None
+    }
+}
+
+impl SearchIndex {
+    /// Build the index of all the areas.
+    pub fn new(areas: &Areas) -> SearchIndex {
+        let mut entries = Vec::new();
+        for class in CLASSES {
+            for (code, raw) in areas.areas(&class) {
+                let kana = raw.kana.as_ref().map(|k| to_hiragana(k));
+                entries.push(Entry {
+                    area: Area::new(&class, code, raw),
+                    name: raw.name.clone(),
+                    romaji: kana.as_ref().map(|k| fold_romaji(&to_romaji(k))),
+                    kana,
+                    english: fold_romaji(&raw.en_name),
+                });
+            }
+        }
+        entries.sort_by(|a, b| {
+            (class_rank(&a.area.class), &a.area.code)
+                .cmp(&(class_rank(&b.area.class), &b.area.code))
+        });
+        SearchIndex { entries }
+    }
+
+    /// Returns the areas matching the keyword, best first.
+    pub fn search(&self, keyword: &str, class: Option<&JmaAreaClass>) -> Vec<SearchHit> {
+        let keyword = normalize(keyword).trim().to_lowercase();
+        if keyword.is_empty() {
+            return Vec::new();
+        }
+        let hiragana = to_hiragana(&keyword);
+        let romaji = if is_kana(&hiragana) {
+            fold_romaji(&to_romaji(&hiragana))
+        } else {
+            fold_romaji(&keyword)
+        };
+
+        let mut hits: Vec<SearchHit> = self
+            .entries
+            .iter()
+            .filter(|e| class.is_none_or(|c| e.area.class == *c))
+            .filter_map(|e| {
+                let kind = if e.area.code == keyword {
+                    Some(MatchKind::Exact)
+                } else {
+                    [
+                        match_kind(&keyword, &e.name),
+                        e.kana.as_ref().and_then(|k| match_kind(&hiragana, k)),
+                        match_kind(&romaji, &e.english),
+                        e.romaji.as_ref().and_then(|r| match_kind(&romaji, r)),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                };
+                kind.map(|kind| SearchHit {
+                    area: e.area.clone(),
+                    kind,
+                })
+            })
+            .collect();
+        // The entries are already ordered by class and code.
+        hits.sort_by_key(|hit| hit.kind);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas() -> Areas {
+        Areas::from_value(serde_json::json!({
+            "centers": {
+                "010200": {"name": "東北地方", "enName": "Tohoku", "officeName": "仙台管区気象台", "children": ["050000"]}
+            },
+            "offices": {
+                "050000": {"name": "秋田県", "enName": "Akita", "officeName": "秋田地方気象台", "parent": "010200", "children": ["050010"]}
+            },
+            "class10s": {
+                "050010": {"name": "沿岸", "enName": "Coast", "parent": "050000", "children": ["050011"]}
+            },
+            "class15s": {
+                "050011": {"name": "秋田中央地域", "enName": "Akita Central Area", "parent": "050010", "children": ["0520100", "0521100"]}
+            },
+            "class20s": {
+                "0520100": {"name": "秋田市", "enName": "Akita City", "kana": "あきたし", "parent": "050011"},
+                "0521100": {"name": "潟上市", "enName": "Katagami City", "kana": "かたがみし", "parent": "050011"},
+                "1301100": {"name": "東京都新宿区", "enName": "Shinjuku City", "kana": "しんじゅくく", "parent": "130011"},
+                "2710000": {"name": "大阪市", "enName": "Osaka City", "kana": "おおさかし", "parent": "270011"}
+            }
+        }))
+        .unwrap()
+    }
+
+    fn codes(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.area.code.as_str()).collect()
+    }
+
+    #[test]
+    fn romaji() {
+        assert_eq!(to_romaji("しんじゅくく"), "shinjukuku");
+        assert_eq!(to_romaji("きょうと"), "kyouto");
+        assert_eq!(to_romaji("ちゃたんちょう"), "chatanchou");
+        assert_eq!(to_romaji("ろっぽんぎ"), "roppongi");
+        assert_eq!(to_romaji("ハッチョウボリ"), "hatchoubori");
+        assert_eq!(fold_romaji("Tōkyō"), fold_romaji(&to_romaji("とうきょう")));
+        assert_eq!(fold_romaji("Osaka"), fold_romaji(&to_romaji("おおさか")));
+    }
+
+    #[test]
+    fn same_top_hit() {
+        let index = SearchIndex::new(&areas());
+        for keyword in ["akita", "Akita", "あきた", "アキタ", "秋田", "ａｋｉｔａ"] {
+            let hits = index.search(keyword, None);
+            assert_eq!(hits[0].area.code, "050000", "{}", keyword);
+            // Deduplicated
+            let mut codes = codes(&hits);
+            let len = codes.len();
+            codes.dedup();
+            assert_eq!(codes.len(), len);
+        }
+    }
+
+    #[test]
+    fn ranking() {
+        let index = SearchIndex::new(&areas());
+        let hits = index.search("akita", None);
+        assert_eq!(codes(&hits), ["050000", "050011", "0520100"]);
+        assert_eq!(hits[0].kind, MatchKind::Exact);
+        assert_eq!(hits[1].kind, MatchKind::Prefix);
+
+        let hits = index.search("050010", None);
+        assert_eq!(codes(&hits), ["050010"]);
+        assert_eq!(codes(&index.search("中央", None)), ["050011"]);
+        assert_eq!(index.search("中央", None)[0].kind, MatchKind::Substring);
+        assert_eq!(codes(&index.search("shinjuku", None)), ["1301100"]);
+        assert_eq!(codes(&index.search("おおさか", None)), ["2710000"]);
+
+        let hits = index.search("katagam", None);
+        assert_eq!(hits[0].kind, MatchKind::Prefix);
+        let hits = index.search("katagma", None);
+        assert_eq!(codes(&hits), ["0521100"]);
+        assert_eq!(hits[0].kind, MatchKind::Fuzzy);
+
+        let hits = index.search("akita", Some(&JmaAreaClass::Class20));
+        assert_eq!(codes(&hits), ["0520100"]);
+        assert!(index.search(" ", None).is_empty());
+    }
+}
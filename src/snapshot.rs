@@ -0,0 +1,244 @@
+//! # Table Changes
+//!
+//! Compare saved copies of `area.json`, `forecast_area.json` and `amedastable.json` with each
+//! other or with the current tables, and report the codes added, removed or renamed.
+//! Save the tables with `cargo run --example snapshot -- <dir>` and load them with
+//! `Areas::from_reader`, `ForecastArea::from_reader` and `AmedasStations::from_reader`.
+//!
+//! ## Example
+//! ```rust
+//! use jma::area::Areas;
+//! use jma::snapshot::compare_areas;
+//!
+//! let previous = Areas::from_value(serde_json::json!({
+//!     "centers": {}, "offices": {}, "class10s": {}, "class15s": {},
+//!     "class20s": {"1020100": {"name": "前橋市", "enName": "Maebashi City", "parent": "100011"}}
+//! }))
+//! .unwrap();
+//! let current = Areas::from_value(serde_json::json!({
+//!     "centers": {}, "offices": {}, "class10s": {}, "class15s": {},
+//!     "class20s": {"1020100": {"name": "新前橋市", "enName": "Shin-Maebashi City", "parent": "100011"}}
+//! }))
+//! .unwrap();
+//! let changes = compare_areas(&previous, &current);
+//! assert_eq!(changes[0].to_string(), "class20s 1020100 renamed from 前橋市 to 新前橋市");
+//! ```
+
+use crate::amedas::AmedasStations;
+use crate::area::{Areas, JmaAreaClass};
+#[cfg(feature = "http")]
+use crate::client::ClientError;
+use crate::forecast_area::ForecastArea;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub enum CodeChangeKind {
+    Added,
+    Removed,
+    /// The name of the code changed.
+    Renamed,
+    /// The stations or the class20 of the forecast area changed.
+    Modified,
+}
+
+/// Difference of a code between two tables.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CodeChange {
+    /// e.g. "class20s", "forecast_area", "amedastable"
+    pub table: String,
+    pub code: String,
+    pub kind: CodeChangeKind,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+impl fmt::Display for CodeChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let previous = self.previous.as_deref().unwrap_or("-");
+        let current = self.current.as_deref().unwrap_or("-");
+        match self.kind {
+            CodeChangeKind::Added => write!(f, "{} {} added: {}", self.table, self.code, current),
+            CodeChangeKind::Removed => {
+                write!(f, "{} {} removed: {}", self.table, self.code, previous)
+            }
+            CodeChangeKind::Renamed => write!(
+                f,
+                "{} {} renamed from {} to {}",
+                self.table, self.code, previous, current
+            ),
+            CodeChangeKind::Modified => write!(
+                f,
+                "{} {} changed from {} to {}",
+                self.table, self.code, previous, current
+            ),
+        }
+    }
+}
+
+/// Compare the code-to-value maps, ordered by the code.
+fn compare_codes(
+    table: &str,
+    changed: CodeChangeKind,
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<CodeChange> {
+    let mut changes = Vec::new();
+    let codes: std::collections::BTreeSet<&String> =
+        previous.keys().chain(current.keys()).collect();
+    for code in codes {
+        let kind = match (previous.get(code), current.get(code)) {
+            (None, Some(_)) => CodeChangeKind::Added,
+            (Some(_), None) => CodeChangeKind::Removed,
+            (Some(p), Some(c)) if p != c => changed.clone(),
+            _ => continue,
+        };
+        changes.push(CodeChange {
+            table: table.to_string(),
+            code: code.clone(),
+            kind,
+            previous: previous.get(code).cloned(),
+            current: current.get(code).cloned(),
+        });
+    }
+    changes
+}
+
+/// Report the codes added, removed or renamed in area.json.
+pub fn compare_areas(previous: &Areas, current: &Areas) -> Vec<CodeChange> {
+    let names = |areas: &Areas, class: &JmaAreaClass| -> BTreeMap<String, String> {
+        areas
+            .areas(class)
+            .iter()
+            .map(|(code, area)| (code.clone(), area.name.clone()))
+            .collect()
+    };
+    let mut changes = Vec::new();
+    for (class, table) in [
+        (JmaAreaClass::Center, "centers"),
+        (JmaAreaClass::Office, "offices"),
+        (JmaAreaClass::Class10, "class10s"),
+        (JmaAreaClass::Class15, "class15s"),
+        (JmaAreaClass::Class20, "class20s"),
+    ] {
+        changes.extend(compare_codes(
+            table,
+            CodeChangeKind::Renamed,
+            &names(previous, &class),
+            &names(current, &class),
+        ));
+    }
+    changes
+}
+
+/// Report the class10 codes added, removed or modified in forecast_area.json.
+///
+/// The value is written as "{office} {class20} {amedas},...".
+pub fn compare_forecast_areas(previous: &ForecastArea, current: &ForecastArea) -> Vec<CodeChange> {
+    let sites = |forecast_area: &ForecastArea| -> BTreeMap<String, String> {
+        forecast_area
+            .offices
+            .iter()
+            .flat_map(|(office, sites)| {
+                sites.iter().map(move |site| {
+                    (
+                        site.class10.clone(),
+                        format!("{} {} {}", office, site.class20, site.amedas.join(",")),
+                    )
+                })
+            })
+            .collect()
+    };
+    compare_codes(
+        "forecast_area",
+        CodeChangeKind::Modified,
+        &sites(previous),
+        &sites(current),
+    )
+}
+
+/// Report the stations added, removed or renamed in amedastable.json.
+pub fn compare_stations(previous: &AmedasStations, current: &AmedasStations) -> Vec<CodeChange> {
+    let names = |stations: &AmedasStations| -> BTreeMap<String, String> {
+        stations
+            .stations
+            .iter()
+            .map(|(code, station)| (code.clone(), station.kanji_name.clone()))
+            .collect()
+    };
+    compare_codes(
+        "amedastable",
+        CodeChangeKind::Renamed,
+        &names(previous),
+        &names(current),
+    )
+}
+
+#[cfg(feature = "http")]
+/// Fetch the tables from JMA and compare the saved tables with them.
+pub async fn compare_with_latest(
+    areas: &Areas,
+    forecast_area: &ForecastArea,
+    stations: &AmedasStations,
+) -> Result<Vec<CodeChange>, ClientError> {
+    let mut changes = compare_areas(areas, &Areas::new().await?);
+    changes.extend(compare_forecast_areas(
+        forecast_area,
+        &ForecastArea::new().await?,
+    ));
+    changes.extend(compare_stations(stations, &AmedasStations::new().await?));
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas() -> Areas {
+        Areas::from_json_str(include_str!("../testdata/area.json")).unwrap()
+    }
+
+    fn forecast_area() -> ForecastArea {
+        ForecastArea::from_json_str(include_str!("../testdata/forecast_area.json")).unwrap()
+    }
+
+    #[test]
+    fn compare() {
+        let previous = areas();
+        assert!(compare_areas(&previous, &previous).is_empty());
+
+        let current: Areas = serde_json::from_value(serde_json::json!({
+            "centers": {},
+            "offices": {},
+            "class10s": {},
+            "class15s": {},
+            "class20s": {
+                "1020100": {"name": "新前橋市", "enName": "Shin-Maebashi City", "parent": "100011"},
+                "1099900": {"name": "新市", "enName": "New City", "parent": "100011"}
+            }
+        }))
+        .unwrap();
+        let changes = compare_areas(&previous, &current);
+        let class20s: Vec<&CodeChange> = changes.iter().filter(|c| c.table == "class20s").collect();
+        assert_eq!(class20s.len(), 7);
+        assert_eq!(
+            class20s[0].to_string(),
+            "class20s 1020100 renamed from 前橋市 to 新前橋市"
+        );
+        assert_eq!(class20s[1].kind, CodeChangeKind::Removed);
+        assert_eq!(class20s[6].to_string(), "class20s 1099900 added: 新市");
+
+        let current: ForecastArea = serde_json::from_value(serde_json::json!({
+            "200000": [{"class10": "200010", "amedas": ["48156", "48100"], "class20": "2020100"}]
+        }))
+        .unwrap();
+        let changes = compare_forecast_areas(&forecast_area(), &current);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0].to_string(),
+            "forecast_area 200010 changed from 200000 2020100 48156 to 200000 2020100 48156,48100"
+        );
+        assert_eq!(changes[1].kind, CodeChangeKind::Removed);
+    }
+}
@@ -0,0 +1,349 @@
+//! # Store Observations and Forecasts
+//!
+//! Record fetched AMeDAS observations and forecast releases in SQLite.
+//! Enabled by the `store` feature.
+//!
+//! - Observations are deduplicated by the station and the observation time.
+//! - Forecast releases are deduplicated by the office and `reportDatetime`.
+//!
+//! ## Example
+//! ```rust
+//! use jma::amedas::Amedas;
+//! use jma::store::Store;
+//!
+//! let raw = r#"{"temp": [0.4, 0], "humidity": [69, 0], "precipitation10m": [0.0, 0],
+//!     "precipitation1h": [0.0, 0], "windDirection": [0, 0], "wind": [0.0, 0]}"#;
+//! let file = format!(r#"{{"20251118103000": {raw}, "20251118104000": {raw}}}"#);
+//! let amedas = Amedas::from_json_str("14163", &file).unwrap();
+//!
+//! let store = Store::open_in_memory().unwrap();
+//! assert_eq!(store.insert_amedas(&amedas).unwrap(), 2);
+//! // Amedas::update() overwrites amedas.data, so record it every time; duplicates are skipped.
+//! assert_eq!(store.insert_amedas(&amedas).unwrap(), 0);
+//! for observation in store.latest_observations("14163", 6).unwrap() {
+//!     println!("{} {} ℃", observation.time, observation.data.temp.0);
+//! }
+//! ```
+
+use crate::amedas::{Amedas, AmedasRawData};
+use crate::forecast::{JST_OFFSET_SECONDS, JmaForecast};
+use crate::forecast_area::ForecastArea;
+use chrono::{DateTime, FixedOffset, Local, TimeZone};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum StoreError {
+    SqliteError(rusqlite::Error),
+    JsonError(serde_json::Error),
+    NoData(String),
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::SqliteError(e) => write!(f, "SQLite error: {}", e),
+            StoreError::JsonError(e) => write!(f, "JSON error: {}", e),
+            StoreError::NoData(e) => write!(f, "No data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(err: rusqlite::Error) -> StoreError {
+        StoreError::SqliteError(err)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(err: serde_json::Error) -> StoreError {
+        StoreError::JsonError(err)
+    }
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS observations (
+    station    TEXT NOT NULL,
+    time       TEXT NOT NULL,
+    fetched_at TEXT NOT NULL,
+    data       TEXT NOT NULL,
+    PRIMARY KEY (station, time)
+);
+CREATE TABLE IF NOT EXISTS forecasts (
+    office          TEXT NOT NULL,
+    report_datetime TEXT NOT NULL,
+    fetched_at      TEXT NOT NULL,
+    json            TEXT NOT NULL,
+    PRIMARY KEY (office, report_datetime)
+);
+";
+
+/// An AMeDAS observation.
+#[derive(Serialize, Debug, Clone)]
+pub struct Observation {
+    pub station: String,
+    /// "YYYYMMDDhhmmss" in JST, the key of `Amedas.data`.
+    pub time: String,
+    pub fetched_at: String,
+    pub data: AmedasRawData,
+}
+
+/// A forecast release.
+#[derive(Serialize, Debug, Clone)]
+pub struct StoredForecast {
+    pub office: String,
+    pub report_datetime: String,
+    pub fetched_at: String,
+    pub json: Value,
+}
+
+/// Convert the datetime to the key of AMeDAS data ("YYYYMMDDhhmmss" in JST).
+pub fn observation_key<Tz: TimeZone>(datetime: &DateTime<Tz>) -> String {
+    let jst = FixedOffset::east_opt(JST_OFFSET_SECONDS).unwrap();
+    datetime
+        .with_timezone(&jst)
+        .format("%Y%m%d%H%M%S")
+        .to_string()
+}
+
+/// History of observations and forecasts in SQLite.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Open or create the database file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Store, StoreError> {
+        Store::init(Connection::open(path)?)
+    }
+
+    /// Open a database in memory.
+    pub fn open_in_memory() -> Result<Store, StoreError> {
+        Store::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Store, StoreError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Store { connection })
+    }
+
+    /// Record the observations. Returns the number of new observations.
+    pub fn insert_amedas(&self, amedas: &Amedas) -> Result<usize, StoreError> {
+        let fetched_at = Local::now().to_rfc3339();
+        let mut statement = self.connection.prepare_cached(
+            "INSERT OR IGNORE INTO observations (station, time, fetched_at, data) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        let mut inserted = 0;
+        for (time, data) in &amedas.data {
+            let data = serde_json::to_string(data)?;
+            inserted += statement.execute(params![amedas.amedas_code, time, fetched_at, data])?;
+        }
+        Ok(inserted)
+    }
+
+    /// Record the forecast release of the office. Returns false if it is already recorded.
+    pub fn insert_forecast(
+        &self,
+        office: &str,
+        forecast: &JmaForecast,
+    ) -> Result<bool, StoreError> {
+        self.insert_forecast_json(office, forecast.json())
+    }
+
+    /// Record the forecast JSON of the office. Returns false if it is already recorded.
+    pub fn insert_forecast_json(&self, office: &str, json: &Value) -> Result<bool, StoreError> {
+        let report_datetime = match json[0]["reportDatetime"].as_str() {
+            Some(dt) => dt,
+            None => return Err(StoreError::NoData("reportDatetime not found".to_string())),
+        };
+        let inserted = self.connection.execute(
+            "INSERT OR IGNORE INTO forecasts (office, report_datetime, fetched_at, json) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                office,
+                report_datetime,
+                Local::now().to_rfc3339(),
+                serde_json::to_string(json)?
+            ],
+        )?;
+        Ok(inserted == 1)
+    }
+
+    /// Returns the observations of the station in [from, to], oldest first.
+    pub fn observations<Tz: TimeZone>(
+        &self,
+        station: &str,
+        from: &DateTime<Tz>,
+        to: &DateTime<Tz>,
+    ) -> Result<Vec<Observation>, StoreError> {
+        self.query_observations(
+            "SELECT station, time, fetched_at, data FROM observations
+             WHERE station = ?1 AND time >= ?2 AND time <= ?3 ORDER BY time",
+            params![station, observation_key(from), observation_key(to)],
+        )
+    }
+
+    /// Returns the latest n observations of the station, newest first.
+    pub fn latest_observations(
+        &self,
+        station: &str,
+        n: usize,
+    ) -> Result<Vec<Observation>, StoreError> {
+        self.query_observations(
+            "SELECT station, time, fetched_at, data FROM observations
+             WHERE station = ?1 ORDER BY time DESC LIMIT ?2",
+            params![station, n as i64],
+        )
+    }
+
+    /// Returns the observations of the AMeDAS stations in the class10 region in [from, to].
+    pub fn observations_in_class10<Tz: TimeZone>(
+        &self,
+        forecast_area: &ForecastArea,
+        class10_code: &str,
+        from: &DateTime<Tz>,
+        to: &DateTime<Tz>,
+    ) -> Result<Vec<Observation>, StoreError> {
+        let stations = match forecast_area.get_amedas_by_class10(class10_code) {
+            Some(stations) => stations,
+            None => return Err(StoreError::NoData(format!("{} not found", class10_code))),
+        };
+        let mut observations = Vec::new();
+        for station in stations {
+            observations.extend(self.observations(station, from, to)?);
+        }
+        Ok(observations)
+    }
+
+    /// Returns the latest n forecast releases of the office, newest first.
+    pub fn latest_forecasts(
+        &self,
+        office: &str,
+        n: usize,
+    ) -> Result<Vec<StoredForecast>, StoreError> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT office, report_datetime, fetched_at, json FROM forecasts
+             WHERE office = ?1 ORDER BY report_datetime DESC LIMIT ?2",
+        )?;
+        let rows = statement.query_map(params![office, n as i64], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut forecasts = Vec::new();
+        for row in rows {
+            let (office, report_datetime, fetched_at, json) = row?;
+            forecasts.push(StoredForecast {
+                office,
+                report_datetime,
+                fetched_at,
+                json: serde_json::from_str(&json)?,
+            });
+        }
+        Ok(forecasts)
+    }
+
+    fn query_observations(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Observation>, StoreError> {
+        let mut statement = self.connection.prepare_cached(sql)?;
+        let rows = statement.query_map(params, |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut observations = Vec::new();
+        for row in rows {
+            let (station, time, fetched_at, data) = row?;
+            observations.push(Observation {
+                station,
+                time,
+                fetched_at,
+                data: serde_json::from_str(&data)?,
+            });
+        }
+        Ok(observations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn amedas(times: &[&str], temp: f32) -> Amedas {
+        let raw: AmedasRawData = serde_json::from_value(serde_json::json!({
+            "temp": [temp, 0],
+            "humidity": [50.0, 0],
+            "precipitation10m": [0.0, 0],
+            "precipitation1h": [0.0, 0],
+            "windDirection": [4, 0],
+            "wind": [1.5, 0]
+        }))
+        .unwrap();
+        Amedas {
+            amedas_code: "14163".to_string(),
+            data: times
+                .iter()
+                .map(|t| (t.to_string(), raw.clone()))
+                .collect::<HashMap<String, AmedasRawData>>(),
+            latest_time: String::new(),
+        }
+    }
+
+    #[test]
+    fn observations() {
+        let store = Store::open_in_memory().unwrap();
+        let first = amedas(&["20251118090000", "20251118091000"], 1.0);
+        assert_eq!(store.insert_amedas(&first).unwrap(), 2);
+        // The same observation time is recorded once.
+        let second = amedas(&["20251118091000", "20251118092000"], 2.0);
+        assert_eq!(store.insert_amedas(&second).unwrap(), 1);
+
+        let from = DateTime::parse_from_rfc3339("2025-11-18T00:05:00Z").unwrap();
+        let to = DateTime::parse_from_rfc3339("2025-11-18T09:20:00+09:00").unwrap();
+        let range = store.observations("14163", &from, &to).unwrap();
+        assert_eq!(range.len(), 2);
+        assert_eq!(range[0].time, "20251118091000");
+        assert_eq!(range[0].data.temp.0, 1.0);
+
+        let latest = store.latest_observations("14163", 1).unwrap();
+        assert_eq!(latest[0].time, "20251118092000");
+        assert_eq!(latest[0].data.temp.0, 2.0);
+        assert!(store.latest_observations("44132", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn forecasts() {
+        let store = Store::open_in_memory().unwrap();
+        for report_datetime in ["2025-03-28T11:00:00+09:00", "2025-03-28T17:00:00+09:00"] {
+            let json = serde_json::json!([{ "reportDatetime": report_datetime }]);
+            assert!(store.insert_forecast_json("020000", &json).unwrap());
+            assert!(!store.insert_forecast_json("020000", &json).unwrap());
+        }
+        assert!(
+            store
+                .insert_forecast_json("020000", &serde_json::json!([]))
+                .is_err()
+        );
+
+        let latest = store.latest_forecasts("020000", 5).unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].report_datetime, "2025-03-28T17:00:00+09:00");
+        assert_eq!(
+            latest[1].json[0]["reportDatetime"],
+            "2025-03-28T11:00:00+09:00"
+        );
+    }
+}
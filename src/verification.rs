@@ -0,0 +1,507 @@
+//! # Verify Forecasts with AMeDAS Observations
+//!
+//! Compare the forecast of a class10 region with the observations of its temperature point.
+//!
+//! - The temperature point is the AMeDAS station in both `forecast_area.json` (class10 to AMeDAS)
+//!   and `{office}.json: [0].timeSeries[2]` (`Temps.area.code`).
+//! - The lowest temperature is observed from 00:00 to 09:00 JST and the highest from 09:00 to 18:00 JST.
+//! - Precipitation occurred in a 6-hour period if the total was 1 mm or more.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::amedas::Amedas;
+//! use jma::forecast::JmaForecast;
+//! use jma::forecast_area::ForecastArea;
+//! use jma::verification::{score, temperature_point, verify};
+//!
+//! # #[cfg(feature = "http")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let (office, class10) = ("016000", "016010");
+//!     let forecast = JmaForecast::new(office).await.unwrap();
+//!     let forecast_area = ForecastArea::new().await.unwrap();
+//!     let point = temperature_point(&forecast, &forecast_area, class10).unwrap();
+//!
+//!     // ... store the forecast and fetch the observations of the day later ...
+//!     let amedas = Amedas::new(&point).await.unwrap();
+//!     let peak = forecast.temperature_forecast(&point).unwrap();
+//!     let date = peak.today.date.clone();
+//!     let verification = verify(&forecast, class10, &point, &date, &amedas.data).unwrap();
+//!     let scores = score(&[verification]);
+//!     println!("temperature bias: {:?}", scores.temp_bias);
+//! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
+//! ```
+
+use crate::amedas::{AMEDAS_WEATHER_JMA_WEATHER_CODES, AmedasRawData};
+use crate::forecast::{JST_OFFSET_SECONDS, JmaForecast, PeakTemp, ShortTermForecast};
+use crate::forecast_area::ForecastArea;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Precipitation (mm) in 6 hours regarded as rain.
+pub const PRECIPITATION_THRESHOLD_MM: f32 = 1.0;
+
+/// Number of 10-minute observations required to verify a 6-hour probability of precipitation.
+pub const MIN_OBSERVATIONS_6H: usize = 30;
+
+/// Weather category of a JMA weather code.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum WeatherCategory {
+    Clear,
+    Cloudy,
+    Rain,
+    Snow,
+}
+
+impl WeatherCategory {
+    /// Category of a JMA weather code such as "201" or "500".
+    pub fn from_jma_code(code: &str) -> Option<WeatherCategory> {
+        match code.chars().next() {
+            Some('1') | Some('5') => Some(WeatherCategory::Clear),
+            Some('2') => Some(WeatherCategory::Cloudy),
+            Some('3') | Some('8') => Some(WeatherCategory::Rain),
+            Some('4') => Some(WeatherCategory::Snow),
+            _ => None,
+        }
+    }
+
+    /// Category of an automatic observed weather code of AMeDAS.
+    pub fn from_amedas_code(code: u32) -> Option<WeatherCategory> {
+        AMEDAS_WEATHER_JMA_WEATHER_CODES
+            .iter()
+            .find(|(c, _, _)| *c == code && code != 999)
+            .and_then(|(_, day, _)| WeatherCategory::from_jma_code(&day.to_string()))
+    }
+}
+
+/// A forecast value and the observed value.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Pair<T> {
+    pub forecast: T,
+    pub observed: T,
+}
+
+/// The verification of a day's forecast for a class10 region.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Verification {
+    pub report_datetime: String,
+    pub class10_code: String,
+    pub amedas_code: String,
+    /// YYYY-MM-DD in JST.
+    pub date: String,
+    pub temp_min: Option<Pair<f32>>,
+    pub temp_max: Option<Pair<f32>>,
+    /// Probability (0.0 to 1.0) and whether precipitation occurred.
+    pub pops: Vec<Pair<f32>>,
+    pub weather: Option<Pair<WeatherCategory>>,
+}
+
+/// Scores over a history of verifications.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct Scores {
+    pub temp_count: usize,
+    /// Mean absolute error (℃).
+    pub temp_mae: Option<f32>,
+    /// Mean error, forecast - observed (℃).
+    pub temp_bias: Option<f32>,
+    pub pop_count: usize,
+    /// Brier score of the probabilities of precipitation.
+    pub brier: Option<f32>,
+    pub weather_count: usize,
+    /// Rate of the weather categories forecast correctly.
+    pub hit_rate: Option<f32>,
+}
+
+/// Returns the temperature point of the class10 region.
+pub fn temperature_point(
+    forecast: &JmaForecast,
+    forecast_area: &ForecastArea,
+    class10_code: &str,
+) -> Option<String> {
+    let points = forecast.get_temperature_points()?;
+    forecast_area
+        .get_amedas_by_class10(class10_code)?
+        .iter()
+        .find(|code| points.iter().any(|p| &p.area.code == *code))
+        .cloned()
+}
+
+/// Verify the forecast of the class10 region for the date with the observations of the temperature point.
+///
+/// `observations` is `Amedas.data`, possibly merged from several 3-hour files.
+pub fn verify(
+    forecast: &JmaForecast,
+    class10_code: &str,
+    amedas_code: &str,
+    date: &str,
+    observations: &HashMap<String, AmedasRawData>,
+) -> Option<Verification> {
+    let short_term = forecast.short_term(class10_code)?;
+    let peak = forecast.temperature_forecast(amedas_code);
+    verify_forecast(&short_term, peak.as_ref(), amedas_code, date, observations)
+}
+
+/// Verify the short-term forecast and the peak temperatures with the observations.
+pub fn verify_forecast(
+    short_term: &ShortTermForecast,
+    peak: Option<&PeakTemp>,
+    amedas_code: &str,
+    date: &str,
+    observations: &HashMap<String, AmedasRawData>,
+) -> Option<Verification> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let jst = FixedOffset::east_opt(JST_OFFSET_SECONDS)?;
+    let midnight = jst
+        .from_local_datetime(&day.and_hms_opt(0, 0, 0)?)
+        .single()?;
+    let observed = parse_observations(observations, &jst);
+
+    let daily_peak =
+        peak.and_then(|p| [&p.today, &p.tomorrow].into_iter().find(|d| d.date == date));
+    let observed_min = extreme(&observed, midnight, midnight + Duration::hours(9), f32::min);
+    let observed_max = extreme(
+        &observed,
+        midnight + Duration::hours(9),
+        midnight + Duration::hours(18),
+        f32::max,
+    );
+    let temp_min = daily_peak
+        .and_then(|d| d.lowest.as_ref())
+        .zip(observed_min)
+        .map(|(f, o)| Pair {
+            forecast: f.celsius,
+            observed: o,
+        });
+    let temp_max = daily_peak
+        .and_then(|d| d.highest.as_ref())
+        .zip(observed_max)
+        .map(|(f, o)| Pair {
+            forecast: f.celsius,
+            observed: o,
+        });
+
+    let mut pops = Vec::new();
+    for pop in &short_term.pops {
+        let (start, probability) = match (DateTime::parse_from_rfc3339(&pop.datetime), pop.pop) {
+            (Ok(start), Some(p)) if start.with_timezone(&jst).date_naive() == day => (start, p),
+            _ => continue,
+        };
+        let block: Vec<&(DateTime<FixedOffset>, &AmedasRawData)> = observed
+            .iter()
+            .filter(|(t, _)| *t > start && *t <= start + Duration::hours(6))
+            .collect();
+        if block.len() < MIN_OBSERVATIONS_6H {
+            continue;
+        }
+        let total: f32 = block.iter().map(|(_, d)| d.precipitation10m.0).sum();
+        pops.push(Pair {
+            forecast: probability as f32 / 100.0,
+            observed: if total >= PRECIPITATION_THRESHOLD_MM {
+                1.0
+            } else {
+                0.0
+            },
+        });
+    }
+
+    let forecast_weather = short_term
+        .days
+        .iter()
+        .find(|d| {
+            DateTime::parse_from_rfc3339(&d.datetime)
+                .is_ok_and(|t| t.with_timezone(&jst).date_naive() == day)
+        })
+        .and_then(|d| WeatherCategory::from_jma_code(&d.weather_code));
+    let observed_weather = observed_category(
+        &observed,
+        midnight + Duration::hours(6),
+        midnight + Duration::hours(18),
+    );
+    let weather = forecast_weather.zip(observed_weather).map(|(f, o)| Pair {
+        forecast: f,
+        observed: o,
+    });
+
+    Some(Verification {
+        report_datetime: short_term.report_datetime.clone(),
+        class10_code: short_term.area_code.clone(),
+        amedas_code: amedas_code.to_string(),
+        date: date.to_string(),
+        temp_min,
+        temp_max,
+        pops,
+        weather,
+    })
+}
+
+/// Compute the scores over the verifications.
+pub fn score(history: &[Verification]) -> Scores {
+    let temps: Vec<f32> = history
+        .iter()
+        .flat_map(|v| [v.temp_min, v.temp_max])
+        .flatten()
+        .map(|p| p.forecast - p.observed)
+        .collect();
+    let pops: Vec<f32> = history
+        .iter()
+        .flat_map(|v| v.pops.iter())
+        .map(|p| (p.forecast - p.observed).powi(2))
+        .collect();
+    let hits: Vec<bool> = history
+        .iter()
+        .filter_map(|v| v.weather)
+        .map(|p| p.forecast == p.observed)
+        .collect();
+
+    Scores {
+        temp_count: temps.len(),
+        temp_mae: mean(temps.iter().map(|e| e.abs())),
+        temp_bias: mean(temps.iter().copied()),
+        pop_count: pops.len(),
+        brier: mean(pops.iter().copied()),
+        weather_count: hits.len(),
+        hit_rate: mean(hits.iter().map(|h| if *h { 1.0 } else { 0.0 })),
+    }
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0), |(s, c), v| (s + v, c + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f32)
+    }
+}
+
+/// Observations sorted by time. The keys of AMeDAS data are "YYYYMMDDhhmmss" in JST.
+fn parse_observations<'a>(
+    observations: &'a HashMap<String, AmedasRawData>,
+    jst: &FixedOffset,
+) -> Vec<(DateTime<FixedOffset>, &'a AmedasRawData)> {
+    let mut observed: Vec<(DateTime<FixedOffset>, &AmedasRawData)> = observations
+        .iter()
+        .filter_map(|(key, data)| {
+            let time = NaiveDateTime::parse_from_str(key, "%Y%m%d%H%M%S").ok()?;
+            Some((jst.from_local_datetime(&time).single()?, data))
+        })
+        .collect();
+    observed.sort_by_key(|(t, _)| *t);
+    observed
+}
+
+/// The extreme temperature in (start, end].
+fn extreme(
+    observed: &[(DateTime<FixedOffset>, &AmedasRawData)],
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+    select: fn(f32, f32) -> f32,
+) -> Option<f32> {
+    observed
+        .iter()
+        .filter(|(t, _)| *t > start && *t <= end)
+        .map(|(_, d)| d.temp.0)
+        .reduce(select)
+}
+
+/// The weather category observed in (start, end].
+///
+/// Precipitation observed by the weather codes or the rain gauge takes precedence,
+/// otherwise the most frequent of clear and cloudy.
+fn observed_category(
+    observed: &[(DateTime<FixedOffset>, &AmedasRawData)],
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+) -> Option<WeatherCategory> {
+    let period: Vec<&AmedasRawData> = observed
+        .iter()
+        .filter(|(t, _)| *t > start && *t <= end)
+        .map(|(_, d)| *d)
+        .collect();
+    let categories: Vec<WeatherCategory> = period
+        .iter()
+        .filter_map(|d| d.weather)
+        .filter_map(|w| WeatherCategory::from_amedas_code(w.0))
+        .collect();
+
+    if categories.contains(&WeatherCategory::Snow) {
+        return Some(WeatherCategory::Snow);
+    }
+    let precipitation: f32 = period.iter().map(|d| d.precipitation10m.0).sum();
+    if categories.contains(&WeatherCategory::Rain) || precipitation >= PRECIPITATION_THRESHOLD_MM {
+        return Some(WeatherCategory::Rain);
+    }
+    let clear = categories
+        .iter()
+        .filter(|c| **c == WeatherCategory::Clear)
+        .count();
+    let cloudy = categories
+        .iter()
+        .filter(|c| **c == WeatherCategory::Cloudy)
+        .count();
+    match (clear, cloudy) {
+        (0, 0) => None,
+        (clear, cloudy) if clear >= cloudy => Some(WeatherCategory::Clear),
+        _ => Some(WeatherCategory::Cloudy),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast::{DailyPeakTemp, DailyWeather, Pop, Temperature};
+
+    fn raw(temp: f32, precipitation10m: f32, weather: Option<u32>) -> AmedasRawData {
+        AmedasRawData {
+            pressure: None,
+            temp: (temp, 0),
+            humidity: (50.0, 0),
+            visibility: None,
+            weather: weather.map(|w| (w, 0)),
+            snow1h: None,
+            precipitation10m: (precipitation10m, 0),
+            precipitation1h: (0.0, 0),
+            wind_direction: (None, 0),
+            wind: (None, 0),
+        }
+    }
+
+    /// 10-minute observations on 2025-03-29: 1℃ at 06:00, 9℃ at 14:00, rain 12:00-14:00.
+    fn observations() -> HashMap<String, AmedasRawData> {
+        let mut data = HashMap::new();
+        for minutes in (10..=24 * 60).step_by(10) {
+            let hour = minutes / 60;
+            let temp = if hour <= 14 {
+                1.0 + (hour as f32 - 6.0).abs()
+            } else {
+                23.0 - hour as f32
+            };
+            let rain = if (12 * 60 + 10..=14 * 60).contains(&minutes) {
+                0.5
+            } else {
+                0.0
+            };
+            let weather = if minutes % 60 == 0 {
+                Some(if rain > 0.0 { 7 } else { 1 })
+            } else {
+                None
+            };
+            let key = if minutes == 24 * 60 {
+                "20250330000000".to_string()
+            } else {
+                format!("20250329{:02}{:02}00", hour, minutes % 60)
+            };
+            data.insert(key, raw(temp, rain, weather));
+        }
+        data
+    }
+
+    fn forecast() -> (ShortTermForecast, PeakTemp) {
+        let short_term = ShortTermForecast {
+            report_datetime: "2025-03-28T17:00:00+09:00".to_string(),
+            area_name: "津軽".to_string(),
+            area_code: "020010".to_string(),
+            days: vec![DailyWeather {
+                datetime: "2025-03-29T00:00:00+09:00".to_string(),
+                weather_code: "200".to_string(),
+                weather: "くもり".to_string(),
+                wind: "西の風".to_string(),
+                wave: None,
+            }],
+            pops: ["00", "06", "12", "18"]
+                .iter()
+                .zip([10, 10, 70, 20])
+                .map(|(h, p)| Pop {
+                    datetime: format!("2025-03-29T{}:00:00+09:00", h),
+                    pop: Some(p),
+                })
+                .collect(),
+        };
+        let peak = PeakTemp {
+            report_datetime: "2025-03-28T17:00:00+09:00".to_string(),
+            area_name: "青森".to_string(),
+            area_code: "31312".to_string(),
+            today: DailyPeakTemp {
+                date: "2025-03-28".to_string(),
+                lowest: None,
+                highest: None,
+            },
+            tomorrow: DailyPeakTemp {
+                date: "2025-03-29".to_string(),
+                lowest: Some(Temperature {
+                    celsius: 3.0,
+                    datetime: String::new(),
+                }),
+                highest: Some(Temperature {
+                    celsius: 9.0,
+                    datetime: String::new(),
+                }),
+            },
+        };
+        (short_term, peak)
+    }
+
+    #[test]
+    fn verify_day() {
+        let (short_term, peak) = forecast();
+        let v = verify_forecast(
+            &short_term,
+            Some(&peak),
+            "31312",
+            "2025-03-29",
+            &observations(),
+        )
+        .unwrap();
+        assert_eq!(
+            v.temp_min,
+            Some(Pair {
+                forecast: 3.0,
+                observed: 1.0
+            })
+        );
+        assert_eq!(
+            v.temp_max,
+            Some(Pair {
+                forecast: 9.0,
+                observed: 9.0
+            })
+        );
+        assert_eq!(v.pops.len(), 4);
+        assert_eq!(
+            v.pops[2],
+            Pair {
+                forecast: 0.7,
+                observed: 1.0
+            }
+        );
+        assert_eq!(
+            v.pops[3],
+            Pair {
+                forecast: 0.2,
+                observed: 0.0
+            }
+        );
+        assert_eq!(
+            v.weather,
+            Some(Pair {
+                forecast: WeatherCategory::Cloudy,
+                observed: WeatherCategory::Rain
+            })
+        );
+
+        let scores = score(&[v]);
+        assert_eq!(scores.temp_count, 2);
+        assert_eq!(scores.temp_mae, Some(1.0));
+        assert_eq!(scores.temp_bias, Some(1.0));
+        assert_eq!(scores.pop_count, 4);
+        assert!((scores.brier.unwrap() - (0.01 + 0.01 + 0.09 + 0.04) / 4.0).abs() < 1e-6);
+        assert_eq!(scores.hit_rate, Some(0.0));
+    }
+
+    #[test]
+    fn empty_history() {
+        assert_eq!(score(&[]), Scores::default());
+    }
+}
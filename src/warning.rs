@@ -0,0 +1,216 @@
+//! # Weather Warnings and Advisories
+//!
+//! <https://www.jma.go.jp/bosai/warning/data/warning/>{office_code}.json
+//!
+//! ```json
+//! {
+//!   "reportDatetime": "2026-10-18T10:31:00+09:00",
+//!   "publishingOffice": "前橋地方気象台",
+//!   "headlineText": "",
+//!   "areaTypes": [
+//!     { // Class10
+//!       "areas": [
+//!         {"code": "100010", "warnings": [{"code": "14", "status": "継続"}]}
+//!       ]
+//!     },
+//!     { // Class20
+//!       "areas": [
+//!         {"code": "1020100", "warnings": [{"code": "14", "status": "継続"}, {"code": "15", "status": "解除"}]}
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//! An area without warnings has `[{"status": "発表警報・注意報はなし"}]`.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::warning::JmaWarning;
+//!
+//! # #[cfg(feature = "http")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let warning = JmaWarning::new("100000").await.unwrap();
+//!     for w in warning.warnings("1020100") {
+//!         println!("{} {} {}", w.code, w.name, w.status);
+//!     }
+//! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
+//! ```
+
+#[cfg(feature = "http")]
+use crate::client::{ClientError, Resource, default_client};
+#[cfg(feature = "http")]
+use crate::forecast::office_for_url;
+use serde::{Deserialize, Serialize};
+
+/// Warning codes and names.
+pub const WARNING_NAMES: [(&str, &str); 31] = [
+    ("02", "暴風雪警報"),
+    ("03", "大雨警報"),
+    ("04", "洪水警報"),
+    ("05", "暴風警報"),
+    ("06", "大雪警報"),
+    ("07", "波浪警報"),
+    ("08", "高潮警報"),
+    ("10", "大雨注意報"),
+    ("12", "大雪注意報"),
+    ("13", "風雪注意報"),
+    ("14", "雷注意報"),
+    ("15", "強風注意報"),
+    ("16", "波浪注意報"),
+    ("17", "融雪注意報"),
+    ("18", "洪水注意報"),
+    ("19", "高潮注意報"),
+    ("20", "濃霧注意報"),
+    ("21", "乾燥注意報"),
+    ("22", "なだれ注意報"),
+    ("23", "低温注意報"),
+    ("24", "霜注意報"),
+    ("25", "着氷注意報"),
+    ("26", "着雪注意報"),
+    ("27", "その他の注意報"),
+    ("32", "暴風雪特別警報"),
+    ("33", "大雨特別警報"),
+    ("35", "暴風特別警報"),
+    ("36", "大雪特別警報"),
+    ("37", "波浪特別警報"),
+    ("38", "高潮特別警報"),
+    ("39", "その他の特別警報"),
+];
+
+/// Returns the name of the warning code.
+pub fn warning_name(code: &str) -> Option<&'static str> {
+    WARNING_NAMES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+}
+
+/// Status of a cancelled warning.
+pub const CANCELLED: &str = "解除";
+
+/// `areaTypes[.].areas[.].warnings[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WarningItem {
+    pub code: Option<String>,
+    /// 発表, 継続, 解除, 発表警報・注意報はなし, ...
+    pub status: String,
+}
+
+/// `areaTypes[.].areas[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WarningArea {
+    pub code: String,
+    pub warnings: Vec<WarningItem>,
+}
+
+/// `areaTypes[.]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WarningAreaType {
+    pub areas: Vec<WarningArea>,
+}
+
+/// The warnings and advisories of an Office.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JmaWarning {
+    pub report_datetime: String,
+    pub publishing_office: String,
+    #[serde(default)]
+    pub headline_text: String,
+    pub area_types: Vec<WarningAreaType>,
+}
+
+/// A warning in effect.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub code: String,
+    pub name: String,
+    pub status: String,
+}
+
+impl JmaWarning {
+    /// Fetch the warnings of the Office.
+    #[cfg(feature = "http")]
+    pub async fn new(office: &str) -> Result<JmaWarning, ClientError> {
+        let url = format!(
+            "https://www.jma.go.jp/bosai/warning/data/warning/{}.json",
+            office_for_url(office)
+        );
+        default_client().get_json(&url, Resource::Volatile).await
+    }
+
+    /// Parse a warning JSON ({office}.json).
+    pub fn from_json_str(json: &str) -> Result<JmaWarning, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse a warning JSON ({office}.json) from the reader.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<JmaWarning, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Convert the parsed warning JSON.
+    pub fn from_value(json: serde_json::Value) -> Result<JmaWarning, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+
+    /// Returns the warnings in effect in the area (Class10 or Class20).
+    pub fn warnings(&self, area_code: &str) -> Vec<Warning> {
+        let area = self
+            .area_types
+            .iter()
+            .flat_map(|t| &t.areas)
+            .find(|a| a.code == area_code);
+        match area {
+            Some(area) => area
+                .warnings
+                .iter()
+                .filter(|w| w.status != CANCELLED)
+                .filter_map(|w| {
+                    let code = w.code.clone()?;
+                    Some(Warning {
+                        name: warning_name(&code).unwrap_or("").to_string(),
+                        code,
+                        status: w.status.clone(),
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warnings() {
+        let warning = JmaWarning::from_value(serde_json::json!({
+            "reportDatetime": "2026-10-18T10:31:00+09:00",
+            "publishingOffice": "前橋地方気象台",
+            "headlineText": "",
+            "areaTypes": [
+                {"areas": [{"code": "100010", "warnings": [{"code": "14", "status": "継続"}]}]},
+                {"areas": [
+                    {"code": "1020100", "warnings": [
+                        {"code": "14", "status": "継続"},
+                        {"code": "15", "status": "解除"}
+                    ]},
+                    {"code": "1020200", "warnings": [{"status": "発表警報・注意報はなし"}]}
+                ]}
+            ]
+        }))
+        .unwrap();
+        let warnings = warning.warnings("1020100");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].name, "雷注意報");
+        assert_eq!(warnings[0].status, "継続");
+        assert_eq!(warning.warnings("100010").len(), 1);
+        assert!(warning.warnings("1020200").is_empty());
+        assert!(warning.warnings("9999999").is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+//! # Cross Reference of Stations and Areas
+//!
+//! An index between the AMeDAS stations, the Class10 and Class20 areas and the Offices,
+//! built once from the constant tables so that each lookup is a hash map access.
+//!
+//! | Source | Joins |
+//! | :--- | :--- |
+//! | forecast_area.json | Office ↔ Class10 ↔ stations, representative Class20 |
+//! | area.json | Class20 → Class10 (via Class15) |
+//! | amedastable.json | station names |
+//! | {office}.json | the temperature point of each Class10 |
+//!
+//! ## Example
+//! ```rust,no_run
+//! use jma::area::Areas;
+//! use jma::forecast_area::ForecastArea;
+//! use jma::xref::CrossIndex;
+//!
+//! # #[cfg(feature = "http")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let forecast_area = ForecastArea::new().await.unwrap();
+//!     let areas = Areas::new().await.unwrap();
+//!     let index = CrossIndex::new(&forecast_area).with_areas(&areas);
+//!     assert_eq!(index.class10_of_class20("2020100"), Some("200010"));
+//!     assert_eq!(index.station_of_class20("2020100"), Some("48156"));
+//!     assert_eq!(index.office_of_station("48156"), Some("200000"));
+//! }
+//! # #[cfg(not(feature = "http"))]
+//! # fn main() {}
+//! ```
+
+use crate::amedas::AmedasStations;
+use crate::area::{Areas, JmaAreaClass};
+use crate::forecast::JmaForecast;
+use crate::forecast_area::ForecastArea;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A Class10 region and its stations.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Class10Entry {
+    pub office: String,
+    pub class10: String,
+    /// The representative Class20 in forecast_area.json.
+    pub class20: String,
+    /// The stations in the order of forecast_area.json.
+    pub amedas: Vec<String>,
+    /// The temperature point in `{office}.json`, if joined.
+    pub temperature_point: Option<String>,
+}
+
+impl Class10Entry {
+    /// The temperature point, or the first station.
+    pub fn representative_station(&self) -> Option<&str> {
+        self.temperature_point
+            .as_deref()
+            .or_else(|| self.amedas.first().map(|s| s.as_str()))
+    }
+}
+
+/// Index between the stations, the Class10 and Class20 areas and the Offices.
+#[derive(Debug, Clone, Default)]
+pub struct CrossIndex {
+    class10s: HashMap<String, Class10Entry>,
+    /// Class10 codes of the station, ordered by code.
+    station_class10s: HashMap<String, Vec<String>>,
+    /// Class10 codes of the office, ordered by code.
+    office_class10s: HashMap<String, Vec<String>>,
+    class20_class10: HashMap<String, String>,
+    /// Class20 codes of the Class10, ordered by code.
+    class10_class20s: HashMap<String, Vec<String>>,
+    station_names: HashMap<String, String>,
+}
+
+impl CrossIndex {
+    /// Build the index of forecast_area.json.
+    pub fn new(forecast_area: &ForecastArea) -> CrossIndex {
+        let mut index = CrossIndex::default();
+        for (office, sites) in &forecast_area.offices {
+            for site in sites {
+                index.class10s.insert(
+                    site.class10.clone(),
+                    Class10Entry {
+                        office: office.clone(),
+                        class10: site.class10.clone(),
+                        class20: site.class20.clone(),
+                        amedas: site.amedas.clone(),
+                        temperature_point: None,
+                    },
+                );
+                index
+                    .office_class10s
+                    .entry(office.clone())
+                    .or_default()
+                    .push(site.class10.clone());
+                for amedas in &site.amedas {
+                    index
+                        .station_class10s
+                        .entry(amedas.clone())
+                        .or_default()
+                        .push(site.class10.clone());
+                }
+            }
+        }
+        index.office_class10s.values_mut().for_each(|v| v.sort());
+        index.station_class10s.values_mut().for_each(|v| v.sort());
+        index
+    }
+
+    /// Join the Class20 areas of area.json to their Class10.
+    pub fn with_areas(mut self, areas: &Areas) -> CrossIndex {
+        for (code, raw) in areas.areas(&JmaAreaClass::Class20) {
+            let class15 = match &raw.parent {
+                Some(parent) => areas.areas(&JmaAreaClass::Class15).get(parent),
+                None => None,
+            };
+            if let Some(class10) = class15.and_then(|c| c.parent.clone()) {
+                self.class10_class20s
+                    .entry(class10.clone())
+                    .or_default()
+                    .push(code.clone());
+                self.class20_class10.insert(code.clone(), class10);
+            }
+        }
+        self.class10_class20s.values_mut().for_each(|v| v.sort());
+        self
+    }
+
+    /// Join the station names (kanji) of amedastable.json.
+    pub fn with_stations(mut self, stations: &AmedasStations) -> CrossIndex {
+        self.station_names.extend(
+            stations
+                .stations
+                .iter()
+                .map(|(code, station)| (code.clone(), station.kanji_name.clone())),
+        );
+        self
+    }
+
+    /// Join the temperature points of the short-term forecast.
+    ///
+    /// A temperature point is assigned to the Class10 of the office listing the station.
+    pub fn with_forecast(mut self, forecast: &JmaForecast) -> CrossIndex {
+        for temps in forecast.get_temperature_points().unwrap_or_default() {
+            let point = temps.area.code;
+            if let Some(class10s) = self.station_class10s.get(&point) {
+                for class10 in class10s {
+                    if let Some(entry) = self.class10s.get_mut(class10)
+                        && entry.temperature_point.is_none()
+                    {
+                        entry.temperature_point = Some(point.clone());
+                    }
+                }
+            }
+            self.station_names.entry(point).or_insert(temps.area.name);
+        }
+        self
+    }
+
+    /// Get the Class10 region.
+    pub fn class10(&self, class10: &str) -> Option<&Class10Entry> {
+        self.class10s.get(class10)
+    }
+
+    /// Get the stations of the Class10 region.
+    pub fn stations_of_class10(&self, class10: &str) -> &[String] {
+        self.class10s.get(class10).map_or(&[], |e| &e.amedas)
+    }
+
+    /// Get the Class10 regions referring to the station.
+    pub fn class10s_of_station(&self, station: &str) -> &[String] {
+        self.station_class10s.get(station).map_or(&[], |v| v)
+    }
+
+    /// Get the office of the station.
+    pub fn office_of_station(&self, station: &str) -> Option<&str> {
+        let class10 = self.class10s_of_station(station).first()?;
+        self.office_of_class10(class10)
+    }
+
+    /// Get the office of the Class10 region.
+    pub fn office_of_class10(&self, class10: &str) -> Option<&str> {
+        self.class10s.get(class10).map(|e| e.office.as_str())
+    }
+
+    /// Get the Class10 regions of the office.
+    pub fn class10s_of_office(&self, office: &str) -> &[String] {
+        self.office_class10s.get(office).map_or(&[], |v| v)
+    }
+
+    /// Get the Class10 region of the Class20 (needs [`CrossIndex::with_areas`]).
+    pub fn class10_of_class20(&self, class20: &str) -> Option<&str> {
+        self.class20_class10.get(class20).map(|s| s.as_str())
+    }
+
+    /// Get the Class20 areas of the Class10 region (needs [`CrossIndex::with_areas`]).
+    pub fn class20s_of_class10(&self, class10: &str) -> &[String] {
+        self.class10_class20s.get(class10).map_or(&[], |v| v)
+    }
+
+    /// Get the Class20 areas of the Class10 regions referring to the station.
+    pub fn class20s_of_station(&self, station: &str) -> Vec<&str> {
+        self.class10s_of_station(station)
+            .iter()
+            .flat_map(|class10| self.class20s_of_class10(class10))
+            .map(|s| s.as_str())
+            .collect()
+    }
+
+    /// Get the representative station of the Class20: the temperature point of its Class10,
+    /// or the first station.
+    pub fn station_of_class20(&self, class20: &str) -> Option<&str> {
+        let class10 = self.class10_of_class20(class20)?;
+        self.class10s.get(class10)?.representative_station()
+    }
+
+    /// Get the station name (kanji).
+    pub fn station_name(&self, station: &str) -> Option<&str> {
+        self.station_names.get(station).map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_index() {
+        let forecast_area = ForecastArea::from_value(serde_json::json!({
+            "100000": [
+                {"class10": "100010", "amedas": ["42251", "42266"], "class20": "1020100"},
+                {"class10": "100020", "amedas": ["42091"], "class20": "1020700"}
+            ],
+            "200000": [{"class10": "200010", "amedas": ["48156"], "class20": "2020100"}]
+        }))
+        .unwrap();
+        let areas = Areas::from_json_str(include_str!("../testdata/area.json")).unwrap();
+        let stations = AmedasStations::from_value(serde_json::json!({
+            "42251": {"type": "B", "elems": "11112010", "lat": [36, 24.3], "lon": [139, 3.6],
+                      "alt": 112, "kjName": "前橋", "knName": "マエバシ", "enName": "Maebashi"}
+        }))
+        .unwrap();
+        let forecast = JmaForecast::from_value(serde_json::json!([{
+            "reportDatetime": "2026-10-18T11:00:00+09:00",
+            "timeSeries": [{}, {}, {
+                "timeDefines": ["2026-10-18T09:00:00+09:00"],
+                "areas": [
+                    {"area": {"name": "前橋", "code": "42251"}, "temps": ["20"]},
+                    {"area": {"name": "みなかみ", "code": "42091"}, "temps": ["15"]}
+                ]
+            }]
+        }]))
+        .unwrap();
+        let index = CrossIndex::new(&forecast_area)
+            .with_areas(&areas)
+            .with_stations(&stations)
+            .with_forecast(&forecast);
+
+        assert_eq!(index.stations_of_class10("100010"), ["42251", "42266"]);
+        assert_eq!(index.class10s_of_station("42266"), ["100010"]);
+        assert_eq!(index.office_of_station("48156"), Some("200000"));
+        assert_eq!(index.class10s_of_office("100000"), ["100010", "100020"]);
+        assert_eq!(index.class10_of_class20("1020100"), Some("100010"));
+        assert!(
+            index
+                .class20s_of_class10("100010")
+                .contains(&"1020100".to_string())
+        );
+        assert!(index.class20s_of_station("42251").contains(&"1020100"));
+        assert_eq!(index.station_of_class20("1020100"), Some("42251"));
+        assert_eq!(
+            index
+                .class10("100020")
+                .unwrap()
+                .temperature_point
+                .as_deref(),
+            Some("42091")
+        );
+        assert_eq!(index.station_name("42251"), Some("前橋"));
+        assert_eq!(index.station_name("42091"), Some("みなかみ"));
+        assert_eq!(index.station_name("42266"), None);
+        assert!(index.class10s_of_station("99999").is_empty());
+    }
+}